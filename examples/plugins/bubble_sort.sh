@@ -0,0 +1,18 @@
+#!/bin/sh
+# Bubble sort as a plugin: reads the array, then asks the visualizer to compare
+# and swap elements by index.
+read -r n nums
+i=0
+while [ "$i" -lt "$n" ]; do
+    j=0
+    while [ "$j" -lt $((n - i - 1)) ]; do
+        echo "compare $j $((j + 1))"
+        read -r order
+        if [ "$order" = 1 ]; then
+            echo "swap $j $((j + 1))"
+        fi
+        j=$((j + 1))
+    done
+    i=$((i + 1))
+done
+echo done
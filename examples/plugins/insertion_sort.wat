@@ -0,0 +1,26 @@
+;; Insertion sort as a WebAssembly plugin, swapping each element left until it is
+;; in place. Compile it with `wat2wasm insertion_sort.wat`.
+(module
+  (import "env" "len" (func $len (result i32)))
+  (import "env" "compare" (func $compare (param i32 i32) (result i32)))
+  (import "env" "swap" (func $swap (param i32 i32)))
+  (func (export "sort")
+    (local $i i32)
+    (local $j i32)
+    (local.set $i (i32.const 1))
+    (block $done
+      (loop $outer
+        (br_if $done (i32.ge_s (local.get $i) (call $len)))
+        (local.set $j (local.get $i))
+        (block $placed
+          (loop $inner
+            (br_if $placed (i32.eqz (local.get $j)))
+            (br_if $placed
+              (i32.le_s
+                (call $compare (i32.sub (local.get $j) (i32.const 1)) (local.get $j))
+                (i32.const 0)))
+            (call $swap (i32.sub (local.get $j) (i32.const 1)) (local.get $j))
+            (local.set $j (i32.sub (local.get $j) (i32.const 1)))
+            (br $inner)))
+        (local.set $i (i32.add (local.get $i) (i32.const 1)))
+        (br $outer)))))
@@ -0,0 +1,266 @@
+//! The config file, `$XDG_CONFIG_HOME/sorting-visualizer/config.toml` or
+//! `~/.config/sorting-visualizer/config.toml`. Every setting can be left out and
+//! most can be overridden on the command line.
+
+use std::{collections::BTreeMap, env, fs, path::PathBuf, time::Duration};
+
+use anyhow::{anyhow, Context, Result};
+use serde::{Deserialize, Serialize};
+use sorting_visualizer::{plugin::Limits, sorting::Order};
+
+use crate::{
+    keymap::Keymap,
+    renderer::MAX_SIZE,
+    theme::{Theme, ThemeConfig, BUILTIN},
+};
+
+#[derive(Debug, Default, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub playback: Playback,
+    pub rendering: Rendering,
+    pub audio: Audio,
+    pub input: Input,
+    pub keys: Keymap,
+    pub plugins: Plugins,
+    /// Themes defined by the user, by name.
+    pub themes: BTreeMap<String, ThemeConfig>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Playback {
+    /// Milliseconds between two steps while playing.
+    pub tick_rate: u64,
+    /// Whether an algorithm starts playing once selected.
+    pub autoplay: bool,
+}
+
+impl Default for Playback {
+    fn default() -> Self {
+        return Playback {
+            tick_rate: 200,
+            autoplay: true,
+        };
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Rendering {
+    /// Name of the theme to draw with, `dark` unless `NO_COLOR` is set.
+    pub theme: Option<String>,
+    /// Name of the chart shown first, if it fits the terminal.
+    pub chart: String,
+}
+
+impl Default for Rendering {
+    fn default() -> Self {
+        return Rendering {
+            theme: None,
+            chart: "bars".to_string(),
+        };
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Audio {
+    /// Whether the beep on every step starts muted.
+    pub muted: bool,
+}
+
+impl Default for Audio {
+    fn default() -> Self {
+        return Audio { muted: true };
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Input {
+    /// Number of elements to sort.
+    pub size: u16,
+    /// Order to sort in: ascending, descending or mod:<k>.
+    pub order: String,
+    /// Seed of the shuffle, a different one every run when left out.
+    pub seed: Option<u64>,
+}
+
+impl Default for Input {
+    fn default() -> Self {
+        return Input {
+            size: 32,
+            order: "ascending".to_string(),
+            seed: None,
+        };
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Plugins {
+    /// Executables, Rhai scripts and WebAssembly modules to load as algorithms, see
+    /// `sorting_visualizer::plugin`.
+    pub paths: Vec<PathBuf>,
+    /// Compares, swaps and writes a plugin may make on one run before it is stopped.
+    pub max_steps: u64,
+    /// Bytes of memory a WebAssembly plugin may grow to.
+    pub max_memory: usize,
+    /// Milliseconds an executable plugin has to finish before it is killed.
+    pub timeout: u64,
+}
+
+impl Default for Plugins {
+    fn default() -> Self {
+        let limits = Limits::default();
+        return Plugins {
+            paths: Vec::new(),
+            max_steps: limits.max_steps,
+            max_memory: limits.max_memory,
+            timeout: limits.timeout.as_millis() as u64,
+        };
+    }
+}
+
+impl Config {
+    pub fn path() -> Option<PathBuf> {
+        let dir = env::var_os("XDG_CONFIG_HOME")
+            .map(PathBuf::from)
+            .filter(|dir| dir.is_absolute())
+            .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))?;
+        return Some(dir.join("sorting-visualizer").join("config.toml"));
+    }
+
+    /// Reads the config file, or returns the defaults if there is none.
+    pub fn load() -> Result<Config> {
+        let Some(path) = Config::path().filter(|path| path.exists()) else {
+            return Ok(Config::default());
+        };
+        let text = fs::read_to_string(&path)
+            .with_context(|| format!("failed to read {}", path.display()))?;
+        return Config::parse(&text).with_context(|| format!("invalid config {}", path.display()));
+    }
+
+    /// Parses and checks a config.
+    pub fn parse(text: &str) -> Result<Config> {
+        let config: Config = toml::from_str(text)?;
+        config.order()?;
+        if config.input.size > MAX_SIZE {
+            return Err(anyhow!(
+                "size {} is more than the {} elements a chart can draw",
+                config.input.size,
+                MAX_SIZE
+            ));
+        }
+        return Ok(config);
+    }
+
+    pub fn order(&self) -> Result<Order> {
+        return self.input.order.parse();
+    }
+
+    pub fn tick_rate(&self) -> Duration {
+        return Duration::from_millis(self.playback.tick_rate);
+    }
+
+    pub fn limits(&self) -> Limits {
+        return Limits {
+            max_steps: self.plugins.max_steps,
+            max_memory: self.plugins.max_memory,
+            timeout: Duration::from_millis(self.plugins.timeout),
+        };
+    }
+
+    /// Looks up a theme of the config file, then a built-in one.
+    pub fn theme(&self, name: &str) -> Result<Theme> {
+        if let Some(theme) = self.themes.get(name) {
+            return theme.theme(name);
+        }
+        return Theme::builtin(name).ok_or_else(|| {
+            anyhow!(
+                "unknown theme '{}', built-in themes are {}",
+                name,
+                BUILTIN.join(", ")
+            )
+        });
+    }
+
+    /// The config as TOML, with every setting filled in.
+    pub fn dump(&self) -> Result<String> {
+        return Ok(toml::to_string(self)?);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let config = Config::parse(
+            r#"
+            [playback]
+            tick_rate = 50
+
+            [input]
+            order = "mod:3"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(Duration::from_millis(50), config.tick_rate());
+        assert!(config.playback.autoplay);
+        assert!(config.audio.muted);
+        assert_eq!(32, config.input.size);
+        assert!(matches!(config.order().unwrap(), Order::Modulo(3)));
+
+        assert!(Config::parse("[input]\norder = \"sideways\"").is_err());
+        assert_eq!(0, Config::parse("[input]\nsize = 0").unwrap().input.size);
+        assert_eq!(
+            512,
+            Config::parse("[input]\nsize = 512").unwrap().input.size
+        );
+        assert!(Config::parse("[input]\nsize = 513").is_err());
+        assert!(Config::parse("[audio]\nvolume = 11").is_err());
+    }
+
+    #[test]
+    fn test_theme() {
+        let config = Config::parse(
+            r#"
+            [rendering]
+            theme = "dusk"
+
+            [themes.dusk]
+            swap = "magenta"
+
+            [themes.mono]
+            base = "light"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(Some("dusk".to_string()), config.rendering.theme);
+        assert_eq!("dusk", config.theme("dusk").unwrap().name);
+        // Themes of the config file replace built-in ones of the same name.
+        assert!(config.theme("mono").unwrap().colored());
+        assert!(config.theme("dark").unwrap().colored());
+        let error = config.theme("dawn").unwrap_err();
+        assert!(error.to_string().starts_with("unknown theme 'dawn'"));
+    }
+
+    #[test]
+    fn test_dump() {
+        let mut config = Config::parse("[themes.dusk]\nswap = \"magenta\"").unwrap();
+        config.input.seed = Some(7);
+        let dump = config.dump().unwrap();
+        assert!(dump.contains("[playback]\ntick_rate = 200\nautoplay = true\n"));
+        assert!(dump.contains("[themes.dusk]\nswap = \"magenta\"\n"));
+        assert!(dump.contains("[keys]\nquit = [\"ctrl-c\"]\n"));
+        assert!(dump.contains(
+            "[plugins]\npaths = []\nmax_steps = 1000000\nmax_memory = 16777216\ntimeout = 10000\n"
+        ));
+        let parsed = Config::parse(&dump).unwrap();
+        assert_eq!(Some(7), parsed.input.seed);
+        assert_eq!(dump, parsed.dump().unwrap());
+    }
+}
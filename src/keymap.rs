@@ -0,0 +1,314 @@
+//! Actions of the user interface and the keys bound to them, which can be rebound
+//! in the `[keys]` section of the config file.
+
+use std::{collections::BTreeMap, fmt::Display};
+
+use anyhow::{anyhow, Result};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use serde::{Deserialize, Deserializer, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Screen {
+    /// The list of algorithms.
+    Menu,
+    /// An algorithm being played.
+    Player,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Action {
+    Quit,
+    Help,
+    MenuDown,
+    MenuUp,
+    Unselect,
+    NextOrder,
+    Select,
+    Back,
+    TogglePlay,
+    StepForward,
+    StepBack,
+    Mute,
+    NextChart,
+    ToggleWires,
+    ToggleTree,
+    ToggleCalls,
+}
+
+impl Action {
+    pub fn description(&self) -> &'static str {
+        return match self {
+            Action::Quit => "Quit",
+            Action::Help => "Show or hide the key bindings",
+            Action::MenuDown => "Highlight the next algorithm",
+            Action::MenuUp => "Highlight the previous algorithm",
+            Action::Unselect => "Clear the highlight",
+            Action::NextOrder => "Cycle the sort order",
+            Action::Select => "Play the highlighted algorithm",
+            Action::Back => "Back to the menu",
+            Action::TogglePlay => "Toggle play/pause",
+            Action::StepForward => "Forward to the next iteration",
+            Action::StepBack => "Rewind to the previous iteration",
+            Action::Mute => "Mute or unmute the beep sound",
+            Action::NextChart => "Cycle the chart style",
+            Action::ToggleWires => "Toggle the wire diagram of sorting networks",
+            Action::ToggleTree => "Toggle the tree view of heap based sorts",
+            Action::ToggleCalls => "Toggle the call stack of recursive sorts",
+        };
+    }
+
+    /// Whether the action does anything on the screen.
+    pub fn on(&self, screen: Screen) -> bool {
+        return match self {
+            Action::Quit | Action::Help => true,
+            Action::MenuDown
+            | Action::MenuUp
+            | Action::Unselect
+            | Action::NextOrder
+            | Action::Select => screen == Screen::Menu,
+            _ => screen == Screen::Player,
+        };
+    }
+}
+
+/// A key with the control and alt modifiers held, written like `ctrl-c`, `space` or `?`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct Key {
+    code: KeyCode,
+    modifiers: KeyModifiers,
+}
+
+const NAMED_KEYS: [(&str, KeyCode); 13] = [
+    ("space", KeyCode::Char(' ')),
+    ("enter", KeyCode::Enter),
+    ("esc", KeyCode::Esc),
+    ("tab", KeyCode::Tab),
+    ("backspace", KeyCode::Backspace),
+    ("left", KeyCode::Left),
+    ("right", KeyCode::Right),
+    ("up", KeyCode::Up),
+    ("down", KeyCode::Down),
+    ("home", KeyCode::Home),
+    ("end", KeyCode::End),
+    ("pageup", KeyCode::PageUp),
+    ("pagedown", KeyCode::PageDown),
+];
+
+impl Key {
+    const fn new(code: KeyCode) -> Key {
+        return Key {
+            code,
+            modifiers: KeyModifiers::NONE,
+        };
+    }
+
+    /// Whether the key was pressed. Shift is left out as it is part of the character.
+    fn matches(&self, event: &KeyEvent) -> bool {
+        let modifiers = event.modifiers & (KeyModifiers::CONTROL | KeyModifiers::ALT);
+        return event.code == self.code && modifiers == self.modifiers;
+    }
+}
+
+impl TryFrom<String> for Key {
+    type Error = String;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        let mut rest = s.as_str();
+        let mut modifiers = KeyModifiers::NONE;
+        loop {
+            if let Some(key) = rest.strip_prefix("ctrl-").filter(|key| !key.is_empty()) {
+                modifiers |= KeyModifiers::CONTROL;
+                rest = key;
+            } else if let Some(key) = rest.strip_prefix("alt-").filter(|key| !key.is_empty()) {
+                modifiers |= KeyModifiers::ALT;
+                rest = key;
+            } else {
+                break;
+            }
+        }
+        let mut chars = rest.chars();
+        let code = match (chars.next(), chars.next()) {
+            (Some(c), None) => KeyCode::Char(c),
+            _ => NAMED_KEYS
+                .iter()
+                .find(|(name, _)| *name == rest)
+                .map(|&(_, code)| code)
+                .or_else(|| {
+                    let n = rest.strip_prefix('f')?.parse().ok()?;
+                    return (1..=12).contains(&n).then_some(KeyCode::F(n));
+                })
+                .ok_or_else(|| format!("unknown key '{}'", s))?,
+        };
+        return Ok(Key { code, modifiers });
+    }
+}
+
+impl From<Key> for String {
+    fn from(key: Key) -> Self {
+        return key.to_string();
+    }
+}
+
+impl Display for Key {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.modifiers.contains(KeyModifiers::CONTROL) {
+            write!(f, "ctrl-")?;
+        }
+        if self.modifiers.contains(KeyModifiers::ALT) {
+            write!(f, "alt-")?;
+        }
+        if let Some((name, _)) = NAMED_KEYS.iter().find(|(_, code)| *code == self.code) {
+            return write!(f, "{}", name);
+        }
+        return match self.code {
+            KeyCode::Char(c) => write!(f, "{}", c),
+            KeyCode::F(n) => write!(f, "f{}", n),
+            code => write!(f, "{:?}", code),
+        };
+    }
+}
+
+/// The keys bound to every action. Actions left out of the config file keep their
+/// default keys.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Keymap(BTreeMap<Action, Vec<Key>>);
+
+impl Default for Keymap {
+    fn default() -> Self {
+        let char = |c| Key::new(KeyCode::Char(c));
+        let bindings = [
+            (
+                Action::Quit,
+                vec![Key {
+                    code: KeyCode::Char('c'),
+                    modifiers: KeyModifiers::CONTROL,
+                }],
+            ),
+            (Action::Help, vec![char('?')]),
+            (Action::MenuDown, vec![Key::new(KeyCode::Down), char('j')]),
+            (Action::MenuUp, vec![Key::new(KeyCode::Up), char('k')]),
+            (Action::Unselect, vec![Key::new(KeyCode::Left), char('h')]),
+            (Action::NextOrder, vec![char('o')]),
+            (Action::Select, vec![Key::new(KeyCode::Enter)]),
+            (Action::Back, vec![Key::new(KeyCode::Esc)]),
+            (Action::TogglePlay, vec![char(' ')]),
+            (Action::StepForward, vec![Key::new(KeyCode::Right)]),
+            (Action::StepBack, vec![Key::new(KeyCode::Left)]),
+            (Action::Mute, vec![char('m')]),
+            (Action::NextChart, vec![char('v')]),
+            (Action::ToggleWires, vec![char('w')]),
+            (Action::ToggleTree, vec![char('t')]),
+            (Action::ToggleCalls, vec![char('r')]),
+        ];
+        return Keymap(bindings.into_iter().collect());
+    }
+}
+
+impl<'de> Deserialize<'de> for Keymap {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let mut keymap = Keymap::default();
+        keymap
+            .0
+            .extend(BTreeMap::<Action, Vec<Key>>::deserialize(deserializer)?);
+        keymap.check().map_err(serde::de::Error::custom)?;
+        return Ok(keymap);
+    }
+}
+
+impl Keymap {
+    /// The action of the screen bound to the key pressed.
+    pub fn action(&self, screen: Screen, event: &KeyEvent) -> Option<Action> {
+        return self
+            .0
+            .iter()
+            .filter(|(action, _)| action.on(screen))
+            .find(|(_, keys)| keys.iter().any(|key| key.matches(event)))
+            .map(|(&action, _)| action);
+    }
+
+    /// The actions of the screen with their keys.
+    pub fn bindings(&self, screen: Screen) -> Vec<(Action, &[Key])> {
+        return self
+            .0
+            .iter()
+            .filter(|(action, _)| action.on(screen))
+            .map(|(&action, keys)| (action, keys.as_slice()))
+            .collect();
+    }
+
+    /// Fails if a key is bound to two actions of the same screen.
+    fn check(&self) -> Result<()> {
+        for screen in [Screen::Menu, Screen::Player] {
+            let bindings = self.bindings(screen);
+            for (i, &(action, keys)) in bindings.iter().enumerate() {
+                for &(other, other_keys) in &bindings[i + 1..] {
+                    if let Some(key) = keys.iter().find(|key| other_keys.contains(key)) {
+                        return Err(anyhow!(
+                            "key '{}' is bound to both {:?} and {:?}",
+                            key,
+                            action,
+                            other
+                        ));
+                    }
+                }
+            }
+        }
+        return Ok(());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn press(code: KeyCode, modifiers: KeyModifiers) -> KeyEvent {
+        return KeyEvent::new(code, modifiers);
+    }
+
+    #[test]
+    fn test_key() {
+        for name in ["ctrl-c", "space", "?", "left", "alt-ctrl-x", "f5", "-"] {
+            let key = Key::try_from(name.to_string()).unwrap();
+            let again = Key::try_from(key.to_string()).unwrap();
+            assert_eq!(key, again, "{}", name);
+        }
+        assert_eq!(
+            "ctrl-alt-x",
+            Key::try_from("alt-ctrl-x".to_string()).unwrap().to_string()
+        );
+        assert!(Key::try_from("hyper".to_string()).is_err());
+        assert!(Key::try_from("f13".to_string()).is_err());
+    }
+
+    #[test]
+    fn test_action() {
+        let keymap = Keymap::default();
+        let left = press(KeyCode::Left, KeyModifiers::NONE);
+        assert_eq!(Some(Action::Unselect), keymap.action(Screen::Menu, &left));
+        assert_eq!(Some(Action::StepBack), keymap.action(Screen::Player, &left));
+        let ctrl_c = press(KeyCode::Char('c'), KeyModifiers::CONTROL);
+        assert_eq!(Some(Action::Quit), keymap.action(Screen::Player, &ctrl_c));
+        let c = press(KeyCode::Char('c'), KeyModifiers::NONE);
+        assert_eq!(None, keymap.action(Screen::Player, &c));
+        let help = press(KeyCode::Char('?'), KeyModifiers::SHIFT);
+        assert_eq!(Some(Action::Help), keymap.action(Screen::Menu, &help));
+    }
+
+    #[test]
+    fn test_deserialize() {
+        let keymap: Keymap = toml::from_str("toggle_play = [\"p\", \"space\"]").unwrap();
+        let p = press(KeyCode::Char('p'), KeyModifiers::NONE);
+        assert_eq!(Some(Action::TogglePlay), keymap.action(Screen::Player, &p));
+        // Other actions keep their keys.
+        let m = press(KeyCode::Char('m'), KeyModifiers::NONE);
+        assert_eq!(Some(Action::Mute), keymap.action(Screen::Player, &m));
+
+        let error = toml::from_str::<Keymap>("mute = [\"v\"]").unwrap_err();
+        assert!(error.message().contains("key 'v' is bound to both"));
+        // Keys of different screens do not clash.
+        assert!(toml::from_str::<Keymap>("select = [\"space\"]").is_ok());
+        assert!(toml::from_str::<Keymap>("fly = [\"f\"]").is_err());
+    }
+}
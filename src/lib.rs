@@ -1,5 +1,8 @@
-use rand::seq::SliceRandom;
+#![allow(clippy::needless_return)]
 
+use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
+
+pub mod plugin;
 pub mod sorting;
 
 pub fn init_vec(n: usize) -> Vec<i32> {
@@ -10,11 +13,17 @@ pub fn init_vec(n: usize) -> Vec<i32> {
     return v;
 }
 
-pub fn shuffle(v: &mut Vec<i32>) {
+pub fn shuffle(v: &mut [i32]) {
     let mut rng = rand::thread_rng();
     v.shuffle(&mut rng);
 }
 
+/// Shuffles `v` the same way every time for the same `seed`.
+pub fn shuffle_seeded(v: &mut [i32], seed: u64) {
+    let mut rng = StdRng::seed_from_u64(seed);
+    v.shuffle(&mut rng);
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -34,4 +43,14 @@ mod test {
             assert!(v.contains(&i));
         }
     }
+
+    #[test]
+    fn test_shuffle_seeded() {
+        let mut v = init_vec(9);
+        let mut w = init_vec(9);
+        shuffle_seeded(&mut v, 42);
+        shuffle_seeded(&mut w, 42);
+        assert_eq!(v, w);
+        assert_ne!(init_vec(9), v);
+    }
 }
@@ -1,27 +1,174 @@
+#![allow(clippy::needless_return)]
+
+mod config;
+mod keymap;
+mod renderer;
+mod theme;
+
 use anyhow::{anyhow, Result};
+use clap::{value_parser, Args, Parser, Subcommand};
+use config::Config;
 use crossterm::{
-    event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers},
+    cursor,
+    event::{
+        self, DisableMouseCapture, EnableMouseCapture, Event, KeyEvent, KeyEventKind, MouseButton,
+        MouseEvent, MouseEventKind,
+    },
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
-    ExecutableCommand,
 };
+use keymap::{Action, Screen};
 use ratatui::{
     prelude::*,
-    widgets::{self, Block, BorderType::Rounded, Borders, ListItem, ListState, Paragraph},
+    widgets::{
+        self,
+        block::{Position, Title},
+        Block,
+        BorderType::Rounded,
+        Borders, ListItem, ListState, Paragraph, Wrap,
+    },
 };
+use renderer::{centered, grid_text, renderers, Chart, Columns, Renderer, MAX_SIZE};
 use sorting_visualizer::{
-    init_vec, shuffle,
-    sorting::{get_algorithm_func, get_algorithms, AlgorithmContext, Operation},
+    init_vec, plugin, shuffle, shuffle_seeded,
+    sorting::{
+        get_algorithms, record, Algorithm, AlgorithmContext, Family, Operation, OperationKind,
+        Order, Step,
+    },
 };
 use std::{
+    env,
     fmt::Display,
     io::{self, stdout},
     ops::{DerefMut, Index},
-    sync::{Arc, Mutex},
+    panic,
+    path::PathBuf,
+    process,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
     thread,
     time::{Duration, Instant, SystemTime, UNIX_EPOCH},
     vec,
 };
+use theme::{Highlight, Theme};
+
+/// Visualize iterations of sorting algorithms in the terminal
+#[derive(Parser)]
+#[command(version, about)]
+struct Cli {
+    /// Order to sort in: ascending, descending or mod:<k>
+    #[arg(short, long, value_parser = order_arg)]
+    order: Option<String>,
+    /// Number of elements to sort, at most 512
+    #[arg(short = 'n', long, value_parser = value_parser!(u16).range(..=MAX_SIZE as i64))]
+    size: Option<u16>,
+    /// Seed of the shuffle, to sort the same input every run
+    #[arg(long)]
+    seed: Option<u64>,
+    /// Milliseconds between two steps while playing
+    #[arg(long)]
+    tick_rate: Option<u64>,
+    /// Start algorithms paused
+    #[arg(long)]
+    paused: bool,
+    /// Beep on every step
+    #[arg(long)]
+    sound: bool,
+    /// Chart shown first: bars, braille, dots, hue, disparity or horizontal bars
+    #[arg(long)]
+    chart: Option<String>,
+    /// Colour theme: dark, light, high-contrast, colorblind, mono or one defined in the
+    /// config file
+    #[arg(long)]
+    theme: Option<String>,
+    /// Load an executable, a Rhai script ending in .rhai or a WebAssembly module
+    /// ending in .wasm as an algorithm, see the README for how it sorts
+    #[arg(long = "plugin", value_name = "PATH")]
+    plugins: Vec<PathBuf>,
+    #[command(flatten)]
+    filter: Filter,
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// List the algorithms matching the filter
+    List,
+    /// Inspect the configuration
+    Config {
+        #[command(subcommand)]
+        command: ConfigCommand,
+    },
+}
+
+#[derive(Subcommand)]
+enum ConfigCommand {
+    /// Print the configuration in effect, with the command line applied
+    Dump,
+}
+
+impl Cli {
+    /// Overrides the settings of the config file given on the command line.
+    fn apply(&self, config: &mut Config) {
+        if let Some(order) = &self.order {
+            config.input.order = order.clone();
+        }
+        if let Some(size) = self.size {
+            config.input.size = size;
+        }
+        if let Some(seed) = self.seed {
+            config.input.seed = Some(seed);
+        }
+        if let Some(tick_rate) = self.tick_rate {
+            config.playback.tick_rate = tick_rate;
+        }
+        if self.paused {
+            config.playback.autoplay = false;
+        }
+        if self.sound {
+            config.audio.muted = false;
+        }
+        if let Some(chart) = &self.chart {
+            config.rendering.chart = chart.clone();
+        }
+        if let Some(theme) = &self.theme {
+            config.rendering.theme = Some(theme.clone());
+        }
+        config.plugins.paths.extend(self.plugins.iter().cloned());
+    }
+}
+
+/// Checks that an order parses, keeping its text for the config.
+fn order_arg(s: &str) -> Result<String, String> {
+    return s
+        .parse::<Order>()
+        .map(|_| s.to_string())
+        .map_err(|err| err.to_string());
+}
+
+#[derive(Args)]
+struct Filter {
+    /// Only include algorithms of the family
+    #[arg(long, global = true)]
+    family: Option<Family>,
+    /// Only include stable algorithms
+    #[arg(long, global = true)]
+    stable: bool,
+    /// Only include in-place algorithms
+    #[arg(long, global = true)]
+    in_place: bool,
+}
+
+impl Filter {
+    fn matches(&self, algorithm: &Algorithm) -> bool {
+        return self.family.is_none_or(|f| f == algorithm.family)
+            && (!self.stable || algorithm.stable)
+            && (!self.in_place || algorithm.in_place);
+    }
+}
 
 struct List<T: Display> {
     state: ListState,
@@ -74,117 +221,354 @@ impl<T: Display> List<T> {
     }
 }
 
-struct App<'a> {
-    list: List<&'a str>,
+const DEFAULT_MODULUS: i32 = 4;
+
+const PREVIEW_SIZE: usize = 16;
+const PREVIEW_TICK_RATE: Duration = Duration::from_millis(100);
+
+struct Preview {
+    algorithm: &'static Algorithm,
+    order_index: usize,
+    steps: Vec<Step>,
+    index: usize,
+    last_tick: Instant,
+}
+
+impl Preview {
+    fn tick(&mut self) {
+        if self.last_tick.elapsed() >= PREVIEW_TICK_RATE {
+            self.last_tick = Instant::now();
+            self.index = (self.index + 1) % self.steps.len();
+        }
+    }
+}
+
+struct App {
+    list: List<&'static Algorithm>,
     algorithm: Option<AlgorithmUI>,
+    orders: [Order; 3],
+    order_index: usize,
+    preview: Option<Preview>,
+    preview_nums: Vec<i32>,
+    config: Config,
+    theme: Theme,
+    help: bool,
+    /// Where the menu was drawn last, to find the item clicked.
+    list_area: Rect,
 }
 
-impl<'a> App<'a> {
-    fn new(mut list_items: Vec<&'a str>) -> App<'a> {
-        list_items.sort();
+impl App {
+    fn new(
+        mut list_items: Vec<&'static Algorithm>,
+        order: Order,
+        config: Config,
+        theme: Theme,
+    ) -> App {
+        list_items.sort_by_key(|algorithm| algorithm.name);
+        let modulus = match order {
+            Order::Modulo(k) => k,
+            _ => DEFAULT_MODULUS,
+        };
+        let order_index = match order {
+            Order::Descending => 1,
+            Order::Modulo(_) => 2,
+            _ => 0,
+        };
+        let preview_nums = shuffled(PREVIEW_SIZE, config.input.seed);
         App {
             list: List::new(list_items),
             algorithm: Option::None,
+            orders: [Order::Ascending, Order::Descending, Order::Modulo(modulus)],
+            order_index,
+            preview: None,
+            preview_nums,
+            config,
+            theme,
+            help: false,
+            list_area: Rect::default(),
         }
     }
-}
 
-const BLOCK_FULL: char = '\u{2588}';
-const BLOCK_HALF_QUARTER: char = '\u{2586}';
-const BLOCK_HALF: char = '\u{2584}';
-const BLOCK_QUARTER: char = '\u{2582}';
+    /// Records a new preview when the highlighted algorithm or the order has changed.
+    fn update_preview(&mut self) {
+        let selected = self.list.state.selected().or(self.list.last_selected);
+        let Some(i) = selected else {
+            self.preview = None;
+            return;
+        };
+        let algorithm = self.list.items[i];
+        if let Some(preview) = &self.preview {
+            if preview.algorithm.name == algorithm.name && preview.order_index == self.order_index {
+                return;
+            }
+        }
+        // Plugins may take any time or never finish, so they only run when played.
+        let steps = match algorithm.family {
+            Family::Plugin => vec![Step::new(Operation::Noop(), self.preview_nums.clone())],
+            _ => record(algorithm, &self.preview_nums, self.order()),
+        };
+        self.preview = Some(Preview {
+            algorithm,
+            order_index: self.order_index,
+            steps,
+            index: 0,
+            last_tick: Instant::now(),
+        });
+    }
+
+    fn order(&self) -> Order {
+        return self.orders[self.order_index];
+    }
+
+    fn screen(&self) -> Screen {
+        return match self.algorithm {
+            Some(_) => Screen::Player,
+            None => Screen::Menu,
+        };
+    }
+
+    fn next_order(&mut self) {
+        self.order_index = (self.order_index + 1) % self.orders.len();
+    }
+}
 
 struct AlgorithmUI {
     status: Arc<AlgorithmStatus>,
-    blocks: Vec<String>,
     size: (u16, u16),
     auto_next: bool,
     tick_rate: Duration,
     last_tick: Duration,
     muted: bool,
+    wires: bool,
+    tree: bool,
+    calls: bool,
+    /// Whether the theme has colours, for the charts made of them.
+    colored: bool,
+    /// The charts that fit the terminal, none when it is too small.
+    renderers: Vec<Box<dyn Renderer>>,
+    renderer: usize,
+    /// Where the chart and the timeline were drawn last, to find what was clicked.
+    chart_area: Rect,
+    timeline_area: Rect,
+    /// The index of the element clicked last.
+    inspected: Option<usize>,
 }
 
 impl AlgorithmUI {
-    fn new(name: String, size: Rect, tick_rate: Duration) -> Result<AlgorithmUI> {
-        let blocks_size = blocks_size(size)?;
-        Ok(AlgorithmUI {
-            status: Arc::new(AlgorithmStatus::new(name, blocks_size.0 as usize)),
-            blocks: block_strings(blocks_size.0 as usize),
+    fn new(
+        algorithm: &'static Algorithm,
+        order: Order,
+        size: Rect,
+        config: &Config,
+        theme: &Theme,
+    ) -> AlgorithmUI {
+        let n = config.input.size as usize;
+        let blocks_size = blocks_size(n);
+        let status = AlgorithmStatus::new(algorithm, order, shuffled(n, config.input.seed));
+        let mut algorithm_ui = AlgorithmUI {
+            status: Arc::new(status),
             size: blocks_size,
-            auto_next: true,
-            tick_rate,
+            auto_next: config.playback.autoplay,
+            tick_rate: config.tick_rate(),
             last_tick: Duration::ZERO,
-            muted: true,
-        })
+            muted: config.audio.muted,
+            wires: false,
+            tree: false,
+            calls: false,
+            colored: theme.colored(),
+            renderers: Vec::new(),
+            renderer: 0,
+            chart_area: Rect::default(),
+            timeline_area: Rect::default(),
+            inspected: None,
+        };
+        algorithm_ui.layout(size, &config.rendering.chart);
+        return algorithm_ui;
+    }
+
+    /// Keeps the charts that fit the terminal, leaving room for the borders and the
+    /// footer, and leaves out charts made of colours when the theme has none. Shows
+    /// `chart` if it fits, the first chart otherwise.
+    fn layout(&mut self, size: Rect, chart: &str) {
+        let max_height = size.height.saturating_sub(2 + FOOTER_HEIGHT);
+        self.renderers = renderers(&self.status.nums, self.status.order, max_height)
+            .into_iter()
+            .filter(|renderer| {
+                let (width, height) = renderer.size();
+                width + 2 <= size.width
+                    && height + 2 + FOOTER_HEIGHT <= size.height
+                    && (self.colored || !renderer.colored())
+            })
+            .collect();
+        self.renderer = self
+            .renderers
+            .iter()
+            .position(|renderer| renderer.name() == chart)
+            .unwrap_or(0);
     }
 
-    // todo: optimize
-    fn display_text(&self) -> Text {
-        let mut lines = Vec::new();
+    /// Lays the charts out again for a new terminal size, keeping the one shown.
+    fn resize(&mut self, size: Rect) {
+        let chart = self
+            .renderers
+            .get(self.renderer)
+            .map(|renderer| renderer.name());
+        self.layout(size, chart.unwrap_or_default());
+    }
+
+    /// The size of the smallest terminal a chart fits in.
+    fn min_size(&self) -> (u16, u16) {
+        return renderers(&self.status.nums, self.status.order, 1)
+            .iter()
+            .filter(|renderer| self.colored || !renderer.colored())
+            .map(|renderer| {
+                let (width, height) = renderer.size();
+                (width + 2, height + 2 + FOOTER_HEIGHT)
+            })
+            .min_by_key(|&(width, height)| width as u32 * height as u32)
+            .unwrap();
+    }
+
+    fn render_chart(&mut self, frame: &mut Frame, area: Rect, block: Block, theme: &Theme) {
+        let (width, height) = self.renderer().size();
+        self.chart_area = centered(block.inner(area), width, height);
         let index = self.status.index.lock().unwrap();
         let operations = self.status.operations.lock().unwrap();
-        let (operation, nums) = operations.index(*index);
+        let step = operations.index(*index);
+        let mut highlights = worker_highlights(&operations[..=*index], step.nums.len());
+        highlight_operation(&mut highlights, &step.operation);
+        let chart = Chart::new(self.renderer(), &step.nums, &highlights, theme).block(block);
+        frame.render_widget(chart, area);
+    }
 
-        for i in nums.iter() {
-            lines.push(self.blocks[(*i as usize) - 1].clone());
+    /// Remembers the element of the chart at the column and row of the terminal, or
+    /// forgets it when there is none.
+    fn inspect(&mut self, column: u16, row: u16) {
+        let area = self.chart_area;
+        self.inspected = None;
+        if !area.intersects(Rect::new(column, row, 1, 1)) {
+            return;
         }
+        let index = self.status.index.lock().unwrap();
+        let operations = self.status.operations.lock().unwrap();
+        let nums = &operations.index(*index).nums;
+        let Some(renderer) = self.renderers.get(self.renderer) else {
+            return;
+        };
+        self.inspected =
+            renderer.index_at(nums, (column - area.x) as usize, (row - area.y) as usize);
+    }
 
-        let max_height = self.blocks.last().unwrap().chars().count();
-        let mut result = String::new();
-        for i in 0..max_height {
-            for j in lines.iter() {
-                let c = (*j).chars().nth(max_height - i - 1).unwrap_or(' ');
-                result.push(c);
-            }
-            result.push('\n');
-        }
-        let mut text = Text::raw(result);
-        match operation.adjusted() {
-            Operation::Compare(a, b) => {
-                for line in text.lines.iter_mut() {
-                    let line_content = line.spans[0].content.clone();
-                    let line_chars = line_content.chars().into_iter().collect::<Vec<char>>();
-                    let pre = Span::raw(line_chars[..a].iter().collect::<String>());
-                    let a_span = Span::raw(line_chars[a..a + 1].iter().collect::<String>())
-                        .fg(Color::LightCyan);
-                    let mid = Span::raw(line_chars[a + 1..b].iter().collect::<String>());
-                    let b_span = Span::raw(line_chars[b..b + 1].iter().collect::<String>())
-                        .fg(Color::LightCyan);
-                    let last = Span::raw(line_chars[b + 1..].iter().collect::<String>());
-
-                    line.spans = vec![pre, a_span, mid, b_span, last];
-                }
-            }
-            Operation::Swap(a, b) => {
-                for line in text.lines.iter_mut() {
-                    let line_content = line.spans[0].content.clone();
-                    let line_chars = line_content.chars().into_iter().collect::<Vec<char>>();
-                    let pre = Span::raw(line_chars[..a].iter().collect::<String>());
-                    let a_span = Span::raw(line_chars[a..a + 1].iter().collect::<String>())
-                        .fg(Color::LightGreen);
-                    let mid = Span::raw(line_chars[a + 1..b].iter().collect::<String>());
-                    let b_span = Span::raw(line_chars[b..b + 1].iter().collect::<String>())
-                        .fg(Color::LightGreen);
-                    let last = Span::raw(line_chars[b + 1..].iter().collect::<String>());
-
-                    line.spans = vec![pre, a_span, mid, b_span, last];
-                }
-            }
-            Operation::Insert(i) => {
-                for line in text.lines.iter_mut() {
-                    let line_content = line.spans[0].content.clone();
-                    let line_chars = line_content.chars().into_iter().collect::<Vec<char>>();
-                    let pre = Span::raw(line_chars[..i].iter().collect::<String>());
-                    let span = Span::raw(line_chars[i..i + 1].iter().collect::<String>())
-                        .fg(Color::LightYellow);
-                    let last = Span::raw(line_chars[i + 1..].iter().collect::<String>());
-
-                    line.spans = vec![pre, span, last];
+    /// Describes the element clicked last in the current step.
+    fn inspect_info(&self) -> Option<String> {
+        let inspected = self.inspected?;
+        let index = self.status.index.lock().unwrap();
+        let operations = self.status.operations.lock().unwrap();
+        let value = operations.index(*index).nums[inspected];
+        return Some(format!("index: {} value: {}", inspected, value));
+    }
+
+    fn renderer(&self) -> &dyn Renderer {
+        return self.renderers[self.renderer].as_ref();
+    }
+
+    fn next_renderer(&mut self) {
+        if self.renderers.is_empty() {
+            return;
+        }
+        self.renderer = (self.renderer + 1) % self.renderers.len();
+    }
+
+    fn render_buffer(&self, frame: &mut Frame, area: Rect, block: Block, theme: &Theme) {
+        let index = self.status.index.lock().unwrap();
+        let operations = self.status.operations.lock().unwrap();
+        let step = operations.index(*index);
+        let empty = vec![None; step.nums.len()];
+        let buffer = step.buffer.as_ref().unwrap_or(&empty);
+        let highlights = buffer_highlights(buffer.len(), &step.operation);
+        let columns = Columns::new(buffer, &highlights, theme).block(block);
+        frame.render_widget(columns, area);
+    }
+
+    /// Draws the comparator layers recorded so far, highlighting the one of the current step.
+    fn wires_display_text(&self, width: usize, theme: &Theme) -> Text<'_> {
+        let index = self.status.index.lock().unwrap();
+        let operations = self.status.operations.lock().unwrap();
+        let mut layers = Vec::new();
+        let mut current = None;
+        for (i, step) in operations.iter().enumerate() {
+            if let Operation::Layer(pairs) = &step.operation {
+                if i <= *index {
+                    current = Some(layers.len());
                 }
+                layers.push(pairs.as_slice());
             }
-            _ => {}
         }
-        return text;
+        return wires_text(self.status.nums.len(), &layers, current, width, theme);
+    }
+
+    /// Draws the heap of the current step, using the latest heap boundary reported.
+    fn tree_display_text(&self, theme: &Theme) -> Text<'_> {
+        let index = self.status.index.lock().unwrap();
+        let operations = self.status.operations.lock().unwrap();
+        let step = operations.index(*index);
+        let heap = operations[..=*index]
+            .iter()
+            .rev()
+            .find_map(|step| match step.operation {
+                Operation::Heap(start, end) => Some((start, end)),
+                _ => None,
+            });
+        let (start, end) = heap.unwrap_or((0, step.nums.len()));
+        return tree_text(&step.nums, start, end, &step.operation, theme);
+    }
+
+    /// Draws the subproblems entered up to the current step, one row per recursion depth.
+    fn calls_display_text(&self, theme: &Theme) -> (Text<'_>, String) {
+        let index = self.status.index.lock().unwrap();
+        let operations = self.status.operations.lock().unwrap();
+        let (calls, stack) = call_tree(&operations[..=*index]);
+        let max_depth = calls.iter().map(|call| call.0 + 1).max().unwrap_or(0);
+        let title = format!("calls: depth {}/{}", stack.len(), max_depth);
+        let rows = self.size.1 as usize;
+        let text = calls_text(self.status.nums.len(), &calls, &stack, rows, theme);
+        return (text, title);
+    }
+
+    /// Whether the algorithm reports the subproblems it recurses into.
+    fn recursive(&self) -> bool {
+        return self
+            .status
+            .algorithm
+            .operations
+            .contains(&OperationKind::Enter);
+    }
+
+    /// Whether the algorithm builds a binary heap that can be shown as a tree.
+    fn heap(&self) -> bool {
+        return self
+            .status
+            .algorithm
+            .operations
+            .contains(&OperationKind::Heap);
+    }
+
+    /// Whether the algorithm is a sorting network that can be shown as a wire diagram.
+    fn network(&self) -> bool {
+        return self
+            .status
+            .algorithm
+            .operations
+            .contains(&OperationKind::Layer);
+    }
+
+    /// Whether the algorithm merges through an auxiliary buffer that gets its own panel.
+    fn buffered(&self) -> bool {
+        return self
+            .status
+            .algorithm
+            .operations
+            .contains(&OperationKind::Store);
     }
 
     fn tick(&mut self) -> bool {
@@ -199,54 +583,326 @@ impl AlgorithmUI {
     }
 }
 
-fn block_strings(n: usize) -> Vec<String> {
-    let mut v = Vec::new();
-    for i in 1..n + 1 {
-        let mut s = String::new();
-        for _ in 0..i / 4 {
-            s.push(BLOCK_FULL);
+/// Highlights the indices touched by an operation.
+fn highlight_operation(highlights: &mut [Option<Highlight>], operation: &Operation) {
+    match operation.adjusted() {
+        Operation::Compare(a, b) => {
+            highlights[a] = Some(Highlight::Compare);
+            highlights[b] = Some(Highlight::Compare);
+        }
+        Operation::Swap(a, b) => {
+            highlights[a] = Some(Highlight::Swap);
+            highlights[b] = Some(Highlight::Swap);
+        }
+        Operation::Insert(i)
+        | Operation::Write(i)
+        | Operation::Store(i, _)
+        | Operation::Load(_, i) => highlights[i] = Some(Highlight::Insert),
+        Operation::Run(start, end) => highlights[start..end].fill(Some(Highlight::Run)),
+        Operation::Heap(start, end) => highlights[start..end].fill(Some(Highlight::Heap)),
+        Operation::Partition(start, end, pivot) => {
+            highlights[start..end].fill(Some(Highlight::Partition));
+            highlights[pivot] = Some(Highlight::Pivot);
+        }
+        Operation::Layer(pairs) => {
+            for (a, b) in pairs {
+                highlights[a] = Some(Highlight::Compare);
+                highlights[b] = Some(Highlight::Compare);
+            }
+        }
+        Operation::Enter(_, _) | Operation::Exit(_, _) | Operation::Noop() => {}
+    }
+}
+
+/// Highlights each index by the worker of a parallel algorithm that last touched it.
+fn worker_highlights(steps: &[Step], n: usize) -> Vec<Option<Highlight>> {
+    let mut highlights = vec![None; n];
+    for step in steps {
+        let Some(worker) = step.worker else {
+            continue;
+        };
+        let highlight = Some(Highlight::Worker(worker));
+        match &step.operation {
+            Operation::Compare(a, b) | Operation::Swap(a, b) => {
+                highlights[*a] = highlight;
+                highlights[*b] = highlight;
+            }
+            Operation::Insert(i)
+            | Operation::Write(i)
+            | Operation::Store(i, _)
+            | Operation::Load(_, i) => highlights[*i] = highlight,
+            Operation::Run(start, end)
+            | Operation::Partition(start, end, _)
+            | Operation::Heap(start, end) => highlights[*start..*end].fill(highlight),
+            Operation::Layer(pairs) => {
+                for &(a, b) in pairs {
+                    highlights[a] = highlight;
+                    highlights[b] = highlight;
+                }
+            }
+            Operation::Enter(_, _) | Operation::Exit(_, _) | Operation::Noop() => {}
+        }
+    }
+    return highlights;
+}
+
+const WIRE: char = '\u{2500}';
+const WIRE_CROSSING: char = '\u{253C}';
+const COMPARATOR_END: char = '\u{25CF}';
+
+/// Assigns the comparators of a layer to columns so that comparators in the same
+/// column do not overlap, and returns the columns.
+fn comparator_columns(pairs: &[(usize, usize)]) -> Vec<Vec<(usize, usize)>> {
+    let mut columns: Vec<Vec<(usize, usize)>> = Vec::new();
+    for &(a, b) in pairs.iter() {
+        let free = columns
+            .iter_mut()
+            .find(|column| column.iter().all(|&(c, d)| b < c || d < a));
+        match free {
+            Some(column) => column.push((a, b)),
+            None => columns.push(vec![(a, b)]),
+        }
+    }
+    return columns;
+}
+
+/// Draws a sorting network with one horizontal wire per index. Earlier layers are
+/// drawn normally, the current one highlighted and later ones dimmed. The diagram
+/// scrolls to keep the current layer visible within `width`.
+fn wires_text<'a>(
+    n: usize,
+    layers: &[&[(usize, usize)]],
+    current: Option<usize>,
+    width: usize,
+    theme: &Theme,
+) -> Text<'a> {
+    let mut grid: Vec<Vec<(char, Option<Highlight>)>> = vec![vec![(WIRE, None)]; n];
+    let mut current_start = 0;
+    for (i, pairs) in layers.iter().enumerate() {
+        let highlight = match current {
+            Some(c) if i == c => Some(Highlight::Compare),
+            Some(c) if i < c => None,
+            _ => Some(Highlight::Dim),
+        };
+        if Some(i) == current {
+            current_start = grid.first().map_or(0, |row| row.len());
+        }
+        for column in comparator_columns(pairs) {
+            for row in grid.iter_mut() {
+                row.push((WIRE, None));
+            }
+            for (a, b) in column {
+                let x = grid[a].len() - 1;
+                grid[a][x] = (COMPARATOR_END, highlight);
+                grid[b][x] = (COMPARATOR_END, highlight);
+                for row in grid[a + 1..b].iter_mut() {
+                    row[x] = (WIRE_CROSSING, highlight);
+                }
+            }
         }
-        match i % 4 {
-            1 => s.push(BLOCK_QUARTER),
-            2 => s.push(BLOCK_HALF),
-            3 => s.push(BLOCK_HALF_QUARTER),
+        for row in grid.iter_mut() {
+            row.push((WIRE, None));
+        }
+    }
+
+    let total = grid.first().map_or(0, |row| row.len());
+    let offset = current_start
+        .saturating_sub(width / 3)
+        .min(total.saturating_sub(width));
+    let grid: Vec<Vec<(char, Style)>> = grid
+        .iter()
+        .map(|row| {
+            row.iter()
+                .skip(offset)
+                .take(width)
+                .map(|&(c, highlight)| (c, theme.style(highlight)))
+                .collect()
+        })
+        .collect();
+    return grid_text(&grid);
+}
+
+/// Draws `nums[start..]` as a binary tree rooted at `start`. Nodes from `end` on are
+/// no longer part of the heap and dimmed, the nodes of a compare or swap are
+/// highlighted.
+fn tree_text<'a>(
+    nums: &[i32],
+    start: usize,
+    end: usize,
+    operation: &Operation,
+    theme: &Theme,
+) -> Text<'a> {
+    let count = nums.len() - start;
+    if count == 0 {
+        return Text::default();
+    }
+    let cell = nums[start..]
+        .iter()
+        .map(|num| num.to_string().len())
+        .max()
+        .unwrap();
+
+    // Placing nodes in in-order sequence gives every node its own columns.
+    fn in_order(node: usize, count: usize, next: &mut usize, x: &mut [usize]) {
+        if node < count {
+            in_order(2 * node + 1, count, next, x);
+            x[node] = *next;
+            *next += 1;
+            in_order(2 * node + 2, count, next, x);
+        }
+    }
+    let mut x = vec![0; count];
+    in_order(0, count, &mut 0, &mut x);
+    let x: Vec<usize> = x.iter().map(|i| i * cell).collect();
+    let center = |node: usize| x[node] + cell / 2;
+
+    let node_style = |node: usize| {
+        let i = start + node;
+        return theme.style(match operation.adjusted() {
+            Operation::Compare(a, b) if i == a || i == b => Some(Highlight::Compare),
+            Operation::Swap(a, b) if i == a || i == b => Some(Highlight::Swap),
+            _ if i >= end => Some(Highlight::Dim),
+            _ => None,
+        });
+    };
+    let edge_style = |node: usize| theme.style((start + node >= end).then_some(Highlight::Dim));
+
+    let levels = count.ilog2() as usize + 1;
+    let mut grid: Vec<Vec<(char, Style)>> =
+        vec![vec![(' ', Style::default()); count * cell]; 2 * levels - 1];
+    for node in 0..count {
+        let row = 2 * (node + 1).ilog2() as usize;
+        let label = format!("{:>width$}", nums[start + node], width = cell);
+        for (k, c) in label.chars().enumerate() {
+            grid[row][x[node] + k] = (c, node_style(node));
+        }
+
+        let (left, right) = (2 * node + 1, 2 * node + 2);
+        if left >= count {
+            continue;
+        }
+        let connector = &mut grid[row + 1];
+        connector[center(left)] = ('\u{250C}', edge_style(left));
+        for cell in connector[center(left) + 1..center(node)].iter_mut() {
+            *cell = ('\u{2500}', edge_style(left));
+        }
+        if right < count {
+            connector[center(node)] = ('\u{2534}', edge_style(left));
+            for cell in connector[center(node) + 1..center(right)].iter_mut() {
+                *cell = ('\u{2500}', edge_style(right));
+            }
+            connector[center(right)] = ('\u{2510}', edge_style(right));
+        } else {
+            connector[center(node)] = ('\u{2518}', edge_style(left));
+        }
+    }
+
+    return grid_text(&grid);
+}
+
+/// Replays the enter and exit markers of the steps. Returns every subproblem entered
+/// as `(depth, start, end)` and the indices of those still open, innermost last.
+fn call_tree(steps: &[Step]) -> (Vec<(usize, usize, usize)>, Vec<usize>) {
+    let mut calls = Vec::new();
+    let mut stack = Vec::new();
+    for step in steps {
+        match step.operation {
+            Operation::Enter(start, end) => {
+                stack.push(calls.len());
+                calls.push((stack.len() - 1, start, end));
+            }
+            Operation::Exit(_, _) => {
+                stack.pop();
+            }
             _ => {}
         }
-        v.push(s);
     }
-    return v;
+    return (calls, stack);
+}
+
+/// Draws the subproblems as segments under the bars they cover, one row per depth.
+/// Open calls are highlighted, the innermost one in its own colour. Only the
+/// deepest `rows` levels of a deeper stack are shown.
+fn calls_text<'a>(
+    n: usize,
+    calls: &[(usize, usize, usize)],
+    stack: &[usize],
+    rows: usize,
+    theme: &Theme,
+) -> Text<'a> {
+    let first = stack.len().saturating_sub(rows);
+    let mut grid: Vec<Vec<(char, Style)>> = vec![vec![(' ', Style::default()); n]; rows];
+    for (i, &(depth, start, end)) in calls.iter().enumerate() {
+        if depth < first || depth >= first + rows {
+            continue;
+        }
+        let style = theme.style(match stack.iter().position(|&call| call == i) {
+            Some(k) if k + 1 == stack.len() => Some(Highlight::CurrentCall),
+            Some(_) => Some(Highlight::Call),
+            None => None,
+        });
+        let row = &mut grid[depth - first];
+        for cell in row[start..end].iter_mut() {
+            *cell = ('\u{2500}', style);
+        }
+        row[start] = ('\u{251C}', style);
+        row[end - 1] = ('\u{2524}', style);
+    }
+
+    return grid_text(&grid);
+}
+
+/// Highlights the buffer slot of a merging algorithm touched by an operation.
+fn buffer_highlights(n: usize, operation: &Operation) -> Vec<Option<Highlight>> {
+    let mut highlights = vec![None; n];
+    match operation {
+        Operation::Store(_, to) => highlights[*to] = Some(Highlight::Insert),
+        Operation::Load(from, _) => highlights[*from] = Some(Highlight::Insert),
+        _ => {}
+    }
+    return highlights;
 }
 
 const WIDTH: u16 = 32;
 const HEIGHT: u16 = WIDTH / 4;
+const DETAILS_WIDTH: u16 = 40;
+// The timeline, two lines of step info and one for the element clicked.
+const FOOTER_HEIGHT: u16 = 4;
 
-fn blocks_size(s: Rect) -> anyhow::Result<(u16, u16)> {
-    if s.width < WIDTH {
-        return Err(anyhow!("width is too small".to_string()));
-    }
-    if s.height < HEIGHT {
-        return Err(anyhow!("height is too small".to_string()));
+/// Size of the bars of `n` elements, one column each and four per row of height.
+fn blocks_size(n: usize) -> (u16, u16) {
+    return (n as u16, n.div_ceil(4) as u16);
+}
+
+impl Drop for AlgorithmUI {
+    fn drop(&mut self) {
+        self.status.cancelled.store(true, Ordering::Relaxed);
     }
-    return Ok((WIDTH, HEIGHT));
 }
 
 struct AlgorithmStatus {
     nums: Vec<i32>,
-    operations: Mutex<Vec<(Operation, Vec<i32>)>>,
-    name: String,
+    operations: Mutex<Vec<Step>>,
+    algorithm: &'static Algorithm,
+    order: Order,
     index: Mutex<usize>,
+    /// Why the algorithm gave up, if it did.
+    error: Mutex<Option<String>>,
+    /// Set when the player is closed, to stop plugins still running.
+    cancelled: AtomicBool,
 }
 
 impl AlgorithmStatus {
-    fn new(name: String, size: usize) -> AlgorithmStatus {
-        let mut v = init_vec(size);
-        shuffle(&mut v);
-        let operations = Vec::from(vec![(Operation::Noop(), v.clone())]);
+    fn new(algorithm: &'static Algorithm, order: Order, nums: Vec<i32>) -> AlgorithmStatus {
+        let operations = vec![Step::new(Operation::Noop(), nums.clone())];
         return AlgorithmStatus {
-            nums: v,
+            nums,
             operations: Mutex::new(operations),
-            name,
+            algorithm,
+            order,
             index: Mutex::new(0),
+            error: Mutex::new(None),
+            cancelled: AtomicBool::new(false),
         };
     }
 
@@ -263,6 +919,18 @@ impl AlgorithmStatus {
         return false;
     }
 
+    /// Moves to the step, or the last one recorded so far.
+    fn seek(&self, step: usize) {
+        let len = self.operations.lock().unwrap().len();
+        *self.index.lock().unwrap() = step.min(len.saturating_sub(1));
+    }
+
+    /// The index of the current step and the number of steps recorded so far.
+    fn position(&self) -> (usize, usize) {
+        let len = self.operations.lock().unwrap().len();
+        return (*self.index.lock().unwrap(), len);
+    }
+
     fn step_prev(&self) {
         let mut index = self.index.lock().unwrap();
         if *index > 0 {
@@ -270,38 +938,171 @@ impl AlgorithmStatus {
         }
     }
 
-    fn step_info(&self) -> (usize, Operation) {
+    /// Describes the current step, shown while paused.
+    fn step_info(&self) -> String {
         let index = self.index.lock().unwrap();
-        let (operation, _) = self.operations.lock().unwrap().index(*index).clone();
-        return (*index, operation);
+        let operations = self.operations.lock().unwrap();
+        let step = operations.index(*index);
+        let worker = step
+            .worker
+            .map_or(String::new(), |worker| format!(" worker: {}", worker));
+        return format!("step: {}{}\n{}", *index, worker, step.operation.adjusted());
     }
 }
 
 impl AlgorithmContext for AlgorithmStatus {
     fn next(&self, operation: Operation, nums: Vec<i32>) {
-        self.operations.lock().unwrap().push((operation, nums));
+        self.next_step(Step::new(operation, nums));
+    }
+
+    fn next_buffered(&self, operation: Operation, nums: Vec<i32>, buffer: Vec<Option<i32>>) {
+        self.next_step(Step {
+            buffer: Some(buffer),
+            ..Step::new(operation, nums)
+        });
+    }
+
+    fn next_step(&self, step: Step) {
+        self.operations.lock().unwrap().push(step);
+    }
+
+    fn fail(&self, message: String) {
+        *self.error.lock().unwrap() = Some(message);
+    }
+
+    fn cancelled(&self) -> bool {
+        return self.cancelled.load(Ordering::Relaxed);
     }
 }
 
-fn main() -> io::Result<()> {
+/// The built-in algorithms followed by the plugins of the config.
+fn algorithms(config: &Config) -> Result<Vec<&'static Algorithm>> {
+    let mut algorithms = get_algorithms();
+    for path in &config.plugins.paths {
+        let algorithm = plugin::load(path, config.limits())?;
+        if algorithms.iter().any(|other| other.name == algorithm.name) {
+            return Err(anyhow!(
+                "plugin {} has the name of another algorithm",
+                path.display()
+            ));
+        }
+        algorithms.push(algorithm);
+    }
+    return Ok(algorithms);
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+
+    let mut config = Config::load()?;
+    cli.apply(&mut config);
+
+    let algorithms: Vec<&'static Algorithm> = algorithms(&config)?
+        .into_iter()
+        .filter(|algorithm| cli.filter.matches(algorithm))
+        .collect();
+
+    if let Some(Command::List) = cli.command {
+        print_algorithms(&algorithms);
+        return Ok(());
+    }
+    let name = theme_name(config.rendering.theme.take());
+    let theme = config.theme(&name)?;
+    config.rendering.theme = Some(name);
+    let order = config.order()?;
+    let charts = renderers(&init_vec(1), order, 1);
+    if !charts
+        .iter()
+        .any(|chart| chart.name() == config.rendering.chart)
+    {
+        return Err(anyhow!("unknown chart '{}'", config.rendering.chart));
+    }
+
+    if let Some(Command::Config {
+        command: ConfigCommand::Dump,
+    }) = cli.command
+    {
+        print!("{}", config.dump()?);
+        return Ok(());
+    }
+
+    if algorithms.is_empty() {
+        return Err(anyhow!("no algorithm matches the filter"));
+    }
+
+    // Panics would otherwise leave the terminal in raw mode, with the message lost
+    // on the alternate screen.
+    let hook = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        let _ = restore_terminal();
+        hook(info);
+        // A panic in the thread of a sort leaves the interface unusable too.
+        process::exit(101);
+    }));
+
     enable_raw_mode()?;
-    stdout().execute(EnterAlternateScreen)?;
+    execute!(stdout(), EnterAlternateScreen, EnableMouseCapture)?;
     let backend = CrosstermBackend::new(stdout());
     let mut terminal = Terminal::new(backend)?;
 
-    let app = App::new(get_algorithms());
+    let app = App::new(algorithms, order, config, theme);
     let tick_rate = Duration::from_millis(50);
     let res = run_app(&mut terminal, app, tick_rate);
 
+    restore_terminal()?;
+
+    res?;
+
+    Ok(())
+}
+
+/// Leaves raw mode and the alternate screen, and shows the cursor again.
+fn restore_terminal() -> Result<()> {
     disable_raw_mode()?;
-    execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
-    terminal.show_cursor()?;
+    execute!(
+        stdout(),
+        LeaveAlternateScreen,
+        DisableMouseCapture,
+        cursor::Show
+    )?;
+    return Ok(());
+}
+
+/// Picks the theme given on the command line or in the config file. Without one,
+/// a set `NO_COLOR` turns colours off.
+fn theme_name(name: Option<String>) -> String {
+    let no_color = env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty());
+    return name.unwrap_or_else(|| if no_color { "mono" } else { "dark" }.to_string());
+}
 
-    if let Err(err) = res {
-        return Err(err);
+/// The numbers `1..=n` shuffled, the same way every run when a seed is given.
+fn shuffled(n: usize, seed: Option<u64>) -> Vec<i32> {
+    let mut nums = init_vec(n);
+    match seed {
+        Some(seed) => shuffle_seeded(&mut nums, seed),
+        None => shuffle(&mut nums),
     }
+    return nums;
+}
 
-    Ok(())
+fn print_algorithms(algorithms: &[&Algorithm]) {
+    for algorithm in algorithms {
+        println!(
+            "{} {:<10} {:<8} {:<9} {:<11} {:<13} {}",
+            colored::Colorize::bold(format!("{:<16}", algorithm.name).as_str()),
+            algorithm.family,
+            if algorithm.stable {
+                "stable"
+            } else {
+                "unstable"
+            },
+            if algorithm.in_place { "in-place" } else { "" },
+            algorithm.complexity.best,
+            algorithm.complexity.average,
+            algorithm.complexity.worst,
+        );
+        println!("  {}", colored::Colorize::dimmed(algorithm.description));
+    }
 }
 
 fn run_app<B: Backend>(
@@ -314,14 +1115,19 @@ fn run_app<B: Backend>(
         terminal.draw(|f| ui(f, &mut app))?;
         let timeout = tick_rate.saturating_sub(last_tick.elapsed());
         if crossterm::event::poll(timeout)? {
-            if let Event::Key(key) = event::read()? {
-                let action = handle_key_events(key, &mut app, terminal.size()?);
-                match action {
-                    Action::Quit => {
-                        return io::Result::Ok(());
+            let flow = match event::read()? {
+                Event::Key(key) => handle_key_events(key, &mut app, terminal.size()?),
+                Event::Mouse(mouse) => handle_mouse_events(mouse, &mut app, terminal.size()?),
+                Event::Resize(width, height) => {
+                    if let Some(algorithm) = &mut app.algorithm {
+                        algorithm.resize(Rect::new(0, 0, width, height));
                     }
-                    _ => {}
+                    Flow::Tick
                 }
+                _ => Flow::Tick,
+            };
+            if let Flow::Quit = flow {
+                return io::Result::Ok(());
             }
         }
         if last_tick.elapsed() >= tick_rate {
@@ -331,35 +1137,73 @@ fn run_app<B: Backend>(
 }
 
 fn ui(frame: &mut Frame, app: &mut App) {
+    render_screen(frame, app);
+    if app.help {
+        render_help(frame, app);
+    }
+}
+
+fn render_screen(frame: &mut Frame, app: &mut App) {
     match &mut app.algorithm {
         None => {
             let list_items: Vec<widgets::ListItem> = app
                 .list
                 .items
                 .iter()
-                .map(|i| {
-                    let lines = vec![Line::from(i.bold()).alignment(Alignment::Center)];
-                    ListItem::new(lines).style(Style::default().fg(Color::White))
+                .map(|algorithm| {
+                    let lines = vec![
+                        Line::from(algorithm.name.bold()).alignment(Alignment::Center),
+                        Line::from(Span::styled(
+                            format!("{}, {}", algorithm.family, algorithm.complexity.average),
+                            app.theme.style(Some(Highlight::Dim)),
+                        ))
+                        .alignment(Alignment::Center),
+                    ];
+                    ListItem::new(lines).style(app.theme.text_style())
                 })
                 .collect();
 
             let width = WIDTH + 2;
             let height = HEIGHT * 2 + 2;
-            let area_option = center_area(width, height, frame.size());
-            if area_option.is_none() {
-                return;
-            }
-            let area = area_option.unwrap();
+            let (area, details_area) =
+                match center_area(width + DETAILS_WIDTH, height, frame.size()) {
+                    Some(area) => {
+                        let chunks = Layout::default()
+                            .direction(Direction::Horizontal)
+                            .constraints([Constraint::Length(width), Constraint::Min(0)])
+                            .split(area);
+                        (chunks[0], Some(chunks[1]))
+                    }
+                    None => match center_area(width, height, frame.size()) {
+                        Some(area) => (area, None),
+                        None => {
+                            render_too_small(frame, width, height);
+                            return;
+                        }
+                    },
+                };
 
             let list = widgets::List::new(list_items)
-                .block(Block::default().borders(Borders::ALL).border_type(Rounded))
-                .highlight_style(
-                    Style::default()
-                        .bg(Color::DarkGray)
-                        .add_modifier(Modifier::BOLD),
-                );
+                .block(
+                    Block::default()
+                        .borders(Borders::ALL)
+                        .border_type(Rounded)
+                        .title(format!("order: {}", app.order()))
+                        .title_position(Position::Bottom)
+                        .title_alignment(Alignment::Center),
+                )
+                .highlight_style(app.theme.selection_style());
 
             frame.render_stateful_widget(list, area, &mut app.list.state);
+            app.list_area = area;
+
+            if let Some(details_area) = details_area {
+                app.update_preview();
+                if let Some(preview) = &mut app.preview {
+                    preview.tick();
+                }
+                render_details(frame, app, details_area);
+            }
         }
         Some(algorithm) => {
             let next = algorithm.tick();
@@ -367,94 +1211,439 @@ fn ui(frame: &mut Frame, app: &mut App) {
                 beep();
             }
 
+            if algorithm.wires {
+                render_wires(frame, algorithm, &app.theme);
+                return;
+            }
+
+            let s = frame.size();
+            if algorithm.renderers.is_empty() {
+                let (width, height) = algorithm.min_size();
+                render_too_small(frame, width, height);
+                return;
+            }
+            let (chart_width, chart_height) = algorithm.renderer().size();
+            let (chart_width, chart_height) = (chart_width + 2, chart_height + 2);
             let blocks_width = algorithm.size.0 + 2;
             let blocks_height = algorithm.size.1 + 2;
-            let area_option = center_area(blocks_width, blocks_height, frame.size());
-            if area_option.is_none() {
+            let mut width = chart_width;
+            let mut height = chart_height;
+            // Other panels are left out when they do not fit around a large chart.
+            let mut fits = |panel_width: u16, panel_height: u16| {
+                if panel_width > s.width || height + panel_height + FOOTER_HEIGHT > s.height {
+                    return false;
+                }
+                width = width.max(panel_width);
+                height += panel_height;
+                return true;
+            };
+            // The buffer and the call stack keep the bar layout whatever the chart.
+            let buffered = algorithm.buffered() && fits(blocks_width, blocks_height);
+            let calls = algorithm.calls && fits(blocks_width, blocks_height);
+            let tree = algorithm
+                .tree
+                .then(|| algorithm.tree_display_text(&app.theme))
+                .filter(|tree| fits(tree.width() as u16 + 2, tree.height() as u16 + 2));
+            let tree_height = tree.as_ref().map_or(0, |tree| tree.height() as u16 + 2);
+            let Some(area) = center_area(width, height + FOOTER_HEIGHT, s) else {
+                render_too_small(frame, width, height + FOOTER_HEIGHT);
                 return;
+            };
+            let area = Rect { height, ..area };
+            let centered = |y: u16, panel_width: u16, panel_height: u16| Rect {
+                x: area.x + (width - panel_width) / 2,
+                y,
+                width: panel_width,
+                height: panel_height,
+            };
+
+            if let Some(tree) = tree {
+                let paragraph = Paragraph::new(tree).alignment(Alignment::Center).block(
+                    Block::default()
+                        .border_type(Rounded)
+                        .borders(Borders::ALL)
+                        .title("heap")
+                        .title_alignment(Alignment::Left),
+                );
+                let tree_area = Rect {
+                    height: tree_height,
+                    ..area
+                };
+                frame.render_widget(paragraph, tree_area);
             }
-            let area = area_option.unwrap();
 
-            let text = algorithm.display_text();
-            let paragraph = Paragraph::new(text).alignment(Alignment::Center).block(
-                Block::default()
+            let block = Block::default()
+                .border_type(Rounded)
+                .borders(Borders::ALL)
+                .title(format!(
+                    "{} ({})",
+                    algorithm.status.algorithm.name, algorithm.status.order
+                ))
+                .title_alignment(Alignment::Left)
+                .title(
+                    Title::from(algorithm.renderer().name())
+                        .position(Position::Bottom)
+                        .alignment(Alignment::Right),
+                );
+            let chart_area = centered(area.y + tree_height, chart_width, chart_height);
+            algorithm.render_chart(frame, chart_area, block, &app.theme);
+
+            let mut panel_y = chart_area.y + chart_height;
+            if buffered {
+                let block = Block::default()
                     .border_type(Rounded)
                     .borders(Borders::ALL)
-                    .title(algorithm.status.name.clone())
-                    .title_alignment(Alignment::Left),
-            );
-            frame.render_widget(paragraph, area);
-
-            if !algorithm.auto_next {
-                let (step, operation) = algorithm.status.step_info();
-                let info = format!("step: {}\n{}", step, operation.adjusted());
-                let text_info = Text::from(info);
-                let paragraph_info = Paragraph::new(text_info).alignment(Alignment::Left);
-                let next_area = next_area_vertical(area, 2, 1);
-                frame.render_widget(paragraph_info, next_area);
-                return;
+                    .title("buffer")
+                    .title_alignment(Alignment::Left);
+                let buffer_area = centered(panel_y, blocks_width, blocks_height);
+                algorithm.render_buffer(frame, buffer_area, block, &app.theme);
+                panel_y += blocks_height;
             }
+
+            if calls {
+                let (text, title) = algorithm.calls_display_text(&app.theme);
+                let calls = Paragraph::new(text).alignment(Alignment::Center).block(
+                    Block::default()
+                        .border_type(Rounded)
+                        .borders(Borders::ALL)
+                        .title(title)
+                        .title_alignment(Alignment::Left),
+                );
+                frame.render_widget(calls, centered(panel_y, blocks_width, blocks_height));
+            }
+
+            render_footer(frame, algorithm, area);
         }
     }
 }
 
-enum Action {
+/// Draws the timeline below `area`, then the step info while paused and the
+/// element clicked last.
+fn render_footer(frame: &mut Frame, algorithm: &mut AlgorithmUI, area: Rect) {
+    let timeline_area = next_area_vertical(area, 1, 1);
+    let (index, len) = algorithm.status.position();
+    let timeline = timeline_line(index, len, timeline_area.width as usize);
+    frame.render_widget(Paragraph::new(timeline), timeline_area);
+    algorithm.timeline_area = timeline_area;
+
+    let mut lines: Vec<Line> = Vec::new();
+    let error = algorithm.status.error.lock().unwrap().clone();
+    if let Some(error) = error.filter(|_| index + 1 == len) {
+        lines.push(Line::from(format!("failed: {}", error)));
+    } else if !algorithm.auto_next {
+        lines.extend(
+            algorithm
+                .status
+                .step_info()
+                .lines()
+                .map(|line| Line::from(line.to_string())),
+        );
+    }
+    if let Some(inspected) = algorithm.inspect_info() {
+        lines.resize(2, Line::default());
+        lines.push(Line::from(inspected));
+    }
+    let info_area = next_area_vertical(timeline_area, FOOTER_HEIGHT - 1, 0);
+    let info = Paragraph::new(lines).wrap(Wrap { trim: true });
+    frame.render_widget(info, info_area);
+}
+
+/// Draws the position of step `index` of `len` along a line `width` cells wide.
+fn timeline_line<'a>(index: usize, len: usize, width: usize) -> Line<'a> {
+    if width == 0 {
+        return Line::default();
+    }
+    let knob = match len {
+        0 | 1 => 0,
+        _ => index * (width - 1) / (len - 1),
+    };
+    let played = "\u{2501}".repeat(knob);
+    let rest = "\u{2500}".repeat(width - knob - 1);
+    return Line::from(format!("{}\u{25CF}{}", played, rest));
+}
+
+/// The step at column `x` of a timeline `width` cells wide, the nearest one when
+/// there are more steps than cells.
+fn timeline_index(x: usize, len: usize, width: usize) -> usize {
+    if len < 2 || width < 2 {
+        return 0;
+    }
+    let x = x.min(width - 1);
+    return (x * (len - 1) + (width - 1) / 2) / (width - 1);
+}
+
+/// Asks for a terminal of at least `width` by `height` cells.
+fn render_too_small(frame: &mut Frame, width: u16, height: u16) {
+    let s = frame.size();
+    let text = format!(
+        "terminal too small\nneed {}x{}, have {}x{}",
+        width, height, s.width, s.height
+    );
+    let y = s.height.saturating_sub(2) / 2;
+    let area = Rect {
+        y: s.y + y,
+        height: s.height - y,
+        ..s
+    };
+    let paragraph = Paragraph::new(text)
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: true });
+    frame.render_widget(paragraph, area);
+}
+
+/// Lists the key bindings of the current screen over it.
+fn render_help(frame: &mut Frame, app: &App) {
+    let bindings = app.config.keys.bindings(app.screen());
+    let keys: Vec<String> = bindings
+        .iter()
+        .map(|(_, keys)| {
+            keys.iter()
+                .map(|key| key.to_string())
+                .collect::<Vec<String>>()
+                .join(", ")
+        })
+        .collect();
+    let key_width = keys.iter().map(|keys| keys.len()).max().unwrap_or(0);
+    let lines: Vec<Line> = bindings
+        .iter()
+        .zip(keys)
+        .map(|((action, _), keys)| {
+            Line::from(vec![
+                format!(" {:<width$}  ", keys, width = key_width).bold(),
+                format!("{} ", action.description()).into(),
+            ])
+        })
+        .collect();
+    let text = Text::from(lines);
+    let s = frame.size();
+    let width = (text.width() as u16 + 2).min(s.width);
+    let height = (text.height() as u16 + 2).min(s.height);
+    let area = center_area(width, height, s).unwrap();
+    let paragraph = Paragraph::new(text).block(
+        Block::default()
+            .border_type(Rounded)
+            .borders(Borders::ALL)
+            .title("keys")
+            .title_alignment(Alignment::Left),
+    );
+    frame.render_widget(widgets::Clear, area);
+    frame.render_widget(paragraph, area);
+}
+
+fn render_wires(frame: &mut Frame, algorithm: &mut AlgorithmUI, theme: &Theme) {
+    let s = frame.size();
+    let text = algorithm.wires_display_text(s.width.saturating_sub(4) as usize, theme);
+    let width = (text.width() as u16 + 2).max(algorithm.size.0 + 2);
+    // Leaves room for the footer below the diagram.
+    let height = (text.height() as u16 + 2).min(s.height.saturating_sub(FOOTER_HEIGHT));
+    let Some(area) = center_area(width, height + FOOTER_HEIGHT, s) else {
+        // The diagram scrolls and shrinks, down to a row for the wires.
+        render_too_small(frame, width, 3 + FOOTER_HEIGHT);
+        return;
+    };
+    let area = Rect { height, ..area };
+    let paragraph = Paragraph::new(text).block(
+        Block::default()
+            .border_type(Rounded)
+            .borders(Borders::ALL)
+            .title(format!(
+                "{} ({})",
+                algorithm.status.algorithm.name, algorithm.status.order
+            ))
+            .title_alignment(Alignment::Left),
+    );
+    frame.render_widget(paragraph, area);
+    render_footer(frame, algorithm, area);
+}
+
+fn render_details(frame: &mut Frame, app: &App, area: Rect) {
+    let block = Block::default().borders(Borders::ALL).border_type(Rounded);
+    let Some(preview) = &app.preview else {
+        let hint = Span::styled("select an algorithm", app.theme.style(Some(Highlight::Dim)));
+        let hint = Paragraph::new(hint)
+            .alignment(Alignment::Center)
+            .block(block);
+        frame.render_widget(hint, area);
+        return;
+    };
+    let algorithm = preview.algorithm;
+    let inner = block.inner(area);
+    frame.render_widget(block.title(algorithm.name), area);
+
+    let preview_height = PREVIEW_SIZE.div_ceil(4) as u16;
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(7),
+            Constraint::Min(0),
+            Constraint::Length(preview_height),
+        ])
+        .split(inner);
+
+    let yes_no = |b: bool| if b { "yes" } else { "no" };
+    let info = [
+        ("family", algorithm.family.to_string()),
+        ("stable", yes_no(algorithm.stable).to_string()),
+        ("in-place", yes_no(algorithm.in_place).to_string()),
+        ("memory", algorithm.memory.to_string()),
+        ("best", algorithm.complexity.best.to_string()),
+        ("average", algorithm.complexity.average.to_string()),
+        ("worst", algorithm.complexity.worst.to_string()),
+    ];
+    let lines: Vec<Line> = info
+        .into_iter()
+        .map(|(key, value)| {
+            let key = Span::styled(
+                format!(" {:<10}", key),
+                app.theme.style(Some(Highlight::Dim)),
+            );
+            Line::from(vec![key, value.into()])
+        })
+        .collect();
+    frame.render_widget(Paragraph::new(lines), chunks[0]);
+
+    let description = Paragraph::new(algorithm.description)
+        .wrap(Wrap { trim: true })
+        .block(Block::default().padding(widgets::Padding::uniform(1)));
+    frame.render_widget(description, chunks[1]);
+
+    let step = &preview.steps[preview.index];
+    let values: Vec<Option<i32>> = step.nums.iter().map(|&num| Some(num)).collect();
+    let mut highlights = vec![None; values.len()];
+    highlight_operation(&mut highlights, &step.operation);
+    frame.render_widget(Columns::new(&values, &highlights, &app.theme), chunks[2]);
+}
+
+enum Flow {
     Tick,
     Quit,
 }
 
-fn handle_key_events(key: KeyEvent, app: &mut App, size: Rect) -> Action {
-    if key.kind == KeyEventKind::Press {
-        match &mut app.algorithm {
-            None => match key.code {
-                KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
-                    return Action::Quit
+fn handle_key_events(key: KeyEvent, app: &mut App, size: Rect) -> Flow {
+    if key.kind != KeyEventKind::Press {
+        return Flow::Tick;
+    }
+    let action = app.config.keys.action(app.screen(), &key);
+    // Any key closes the help.
+    if app.help && action != Some(Action::Quit) {
+        app.help = false;
+        return Flow::Tick;
+    }
+    let Some(action) = action else {
+        return Flow::Tick;
+    };
+    match action {
+        Action::Quit => return Flow::Quit,
+        Action::Help => app.help = true,
+        _ => {}
+    }
+    match &mut app.algorithm {
+        None => match action {
+            Action::Unselect => app.list.unselect(),
+            Action::MenuDown => app.list.next(),
+            Action::MenuUp => app.list.previous(),
+            Action::NextOrder => app.next_order(),
+            Action::Select => {
+                if let Some(i) = app.list.state.selected() {
+                    start_algorithm(app, i, size);
                 }
-                KeyCode::Left | KeyCode::Char('h') => app.list.unselect(),
-                KeyCode::Down | KeyCode::Char('j') => app.list.next(),
-                KeyCode::Up | KeyCode::Char('k') => app.list.previous(),
-                KeyCode::Enter => {
-                    if let Some(i) = app.list.state.selected() {
-                        let name = app.list.items[i];
-                        let algorithm =
-                            AlgorithmUI::new(name.to_string(), size, Duration::from_millis(200))
-                                .unwrap();
-                        let status = algorithm.status.clone();
-                        let algorithm_func = get_algorithm_func(name);
-                        thread::spawn(move || {
-                            algorithm_func(
-                                status.as_ref().nums.clone().as_mut_slice(),
-                                status.as_ref(),
-                            );
-                        });
-                        app.algorithm = Some(algorithm);
-                    }
+            }
+            _ => {}
+        },
+        Some(algorithm_ui) => match action {
+            Action::Back => app.algorithm = None,
+            Action::StepForward => {
+                let next = algorithm_ui.status.as_ref().step_next();
+                if next && !algorithm_ui.muted {
+                    beep();
                 }
-                _ => {}
-            },
-            Some(algorithm_ui) => match key.code {
-                KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
-                    return Action::Quit
+            }
+            Action::StepBack if !algorithm_ui.auto_next => algorithm_ui.status.as_ref().step_prev(),
+            Action::TogglePlay => algorithm_ui.auto_next = !algorithm_ui.auto_next,
+            Action::Mute => algorithm_ui.muted = !algorithm_ui.muted,
+            Action::NextChart => algorithm_ui.next_renderer(),
+            Action::ToggleWires if algorithm_ui.network() => {
+                algorithm_ui.wires = !algorithm_ui.wires
+            }
+            Action::ToggleTree if algorithm_ui.heap() => algorithm_ui.tree = !algorithm_ui.tree,
+            Action::ToggleCalls if algorithm_ui.recursive() => {
+                algorithm_ui.calls = !algorithm_ui.calls
+            }
+            _ => {}
+        },
+    }
+    return Flow::Tick;
+}
+
+fn handle_mouse_events(mouse: MouseEvent, app: &mut App, size: Rect) -> Flow {
+    let clicked = matches!(mouse.kind, MouseEventKind::Down(MouseButton::Left));
+    if app.help {
+        app.help = !clicked;
+        return Flow::Tick;
+    }
+    match &mut app.algorithm {
+        None => match mouse.kind {
+            MouseEventKind::ScrollDown => app.list.next(),
+            MouseEventKind::ScrollUp => app.list.previous(),
+            _ if clicked => {
+                // Items are two lines high, inside the border of the list.
+                let inner = app.list_area.inner(&Margin::new(1, 1));
+                if !inner.intersects(Rect::new(mouse.column, mouse.row, 1, 1)) {
+                    return Flow::Tick;
                 }
-                KeyCode::Esc => app.algorithm = None,
-                KeyCode::Right => {
-                    let next = algorithm_ui.status.as_ref().step_next();
-                    if next && !algorithm_ui.muted {
-                        beep();
-                    }
+                let i = app.list.state.offset() + (mouse.row - inner.y) as usize / 2;
+                if i >= app.list.items.len() {
+                    return Flow::Tick;
                 }
-                KeyCode::Left => {
-                    if !algorithm_ui.auto_next {
-                        algorithm_ui.status.as_ref().step_prev()
-                    }
+                // Clicking the highlighted algorithm plays it.
+                if app.list.state.selected() == Some(i) {
+                    start_algorithm(app, i, size);
+                } else {
+                    app.list.state.select(Some(i));
                 }
-                KeyCode::Char(' ') => algorithm_ui.auto_next = !algorithm_ui.auto_next,
-                KeyCode::Char('m') => algorithm_ui.muted = !algorithm_ui.muted,
-                _ => {}
-            },
+            }
+            _ => {}
+        },
+        Some(algorithm_ui) => {
+            let dragged = matches!(mouse.kind, MouseEventKind::Drag(MouseButton::Left));
+            let timeline = algorithm_ui.timeline_area;
+            let point = Rect::new(mouse.column, mouse.row, 1, 1);
+            // Clicks seek on the timeline only, drags along its row scrub past its ends.
+            let seek = (clicked && timeline.intersects(point))
+                || (dragged && mouse.row == timeline.y && timeline.width > 0);
+            if seek {
+                let x = mouse.column.saturating_sub(timeline.x) as usize;
+                let (_, len) = algorithm_ui.status.position();
+                algorithm_ui.auto_next = false;
+                algorithm_ui
+                    .status
+                    .seek(timeline_index(x, len, timeline.width as usize));
+            } else if clicked {
+                algorithm_ui.inspect(mouse.column, mouse.row);
+            }
         }
     }
-    return Action::Tick;
+    return Flow::Tick;
+}
+
+/// Plays the algorithm at `i` in the menu, sorting in the background.
+fn start_algorithm(app: &mut App, i: usize, size: Rect) {
+    let algorithm = AlgorithmUI::new(
+        app.list.items[i],
+        app.order(),
+        size,
+        &app.config,
+        &app.theme,
+    );
+    let status = algorithm.status.clone();
+    thread::spawn(move || {
+        (status.as_ref().algorithm.sort)(
+            status.as_ref().nums.clone().as_mut_slice(),
+            status.as_ref().order,
+            status.as_ref(),
+        );
+    });
+    app.algorithm = Some(algorithm);
 }
 
 fn center_area(width: u16, height: u16, s: Rect) -> Option<Rect> {
@@ -481,58 +1670,451 @@ fn beep() {
 
 #[cfg(test)]
 mod tests {
-    use std::borrow::Borrow;
-
     use super::*;
 
+    fn config(size: u16) -> Config {
+        let mut config = Config::default();
+        config.input.size = size;
+        return config;
+    }
+
     #[test]
-    fn test_block_strings() {
-        let blocks = block_strings(10);
-        assert!(blocks.contains(BLOCK_QUARTER.to_string().borrow()));
-        assert!(blocks.contains(BLOCK_HALF.to_string().borrow()));
-        assert!(blocks.contains(BLOCK_HALF_QUARTER.to_string().borrow()));
-        assert!(blocks.contains(BLOCK_FULL.to_string().borrow()));
-        assert!(blocks.contains(
-            format!("{}{}", BLOCK_FULL, BLOCK_QUARTER)
-                .to_string()
-                .borrow()
-        ));
-        assert!(blocks.contains(format!("{}{}", BLOCK_FULL, BLOCK_HALF).to_string().borrow()));
-        assert!(blocks.contains(
-            format!("{}{}", BLOCK_FULL, BLOCK_HALF_QUARTER)
-                .to_string()
-                .borrow()
-        ));
-        assert!(blocks.contains(format!("{}{}", BLOCK_FULL, BLOCK_FULL).to_string().borrow()));
-        assert!(blocks.contains(
-            format!("{}{}{}", BLOCK_FULL, BLOCK_FULL, BLOCK_QUARTER)
-                .to_string()
-                .borrow()
-        ));
-        assert!(blocks.contains(
-            format!("{}{}{}", BLOCK_FULL, BLOCK_FULL, BLOCK_HALF)
-                .to_string()
-                .borrow()
-        ));
+    fn test_buffer_highlights() {
+        let highlights = buffer_highlights(3, &Operation::Store(0, 2));
+        assert_eq!(vec![None, None, Some(Highlight::Insert)], highlights);
+        let highlights = buffer_highlights(3, &Operation::Load(1, 1));
+        assert_eq!(vec![None, Some(Highlight::Insert), None], highlights);
+    }
+
+    #[test]
+    fn test_worker_highlights() {
+        let steps = [
+            Step {
+                worker: Some(1),
+                ..Step::new(Operation::Run(0, 2), vec![1, 2, 3])
+            },
+            Step::new(Operation::Swap(1, 2), vec![1, 3, 2]),
+            Step {
+                worker: Some(0),
+                ..Step::new(Operation::Compare(1, 2), vec![1, 3, 2])
+            },
+        ];
+        assert_eq!(
+            vec![
+                Some(Highlight::Worker(1)),
+                Some(Highlight::Worker(0)),
+                Some(Highlight::Worker(0))
+            ],
+            worker_highlights(&steps, 3)
+        );
+    }
+
+    #[test]
+    fn test_tree_text() {
+        let text = tree_text(
+            &[9, 5, 7, 1],
+            0,
+            3,
+            &Operation::Swap(0, 2),
+            &Theme::default(),
+        );
+        let rows: Vec<String> = text
+            .lines
+            .iter()
+            .map(|line| {
+                line.spans
+                    .iter()
+                    .map(|span| span.content.as_ref())
+                    .collect()
+            })
+            .collect();
+        assert_eq!(vec!["  9 ", " ┌┴┐", " 5 7", "┌┘  ", "1   "], rows);
+        assert_eq!(Some(Color::LightGreen), text.lines[0].spans[1].style.fg);
+        assert_eq!(Some(Color::DarkGray), text.lines[4].spans[0].style.fg);
+    }
+
+    #[test]
+    fn test_call_tree() {
+        let steps: Vec<Step> = [
+            Operation::Enter(0, 4),
+            Operation::Enter(0, 2),
+            Operation::Exit(0, 2),
+            Operation::Enter(2, 4),
+        ]
+        .into_iter()
+        .map(|operation| Step::new(operation, vec![1, 2, 3, 4]))
+        .collect();
+        let (calls, stack) = call_tree(&steps);
+        assert_eq!(vec![(0, 0, 4), (1, 0, 2), (1, 2, 4)], calls);
+        assert_eq!(vec![0, 2], stack);
+    }
+
+    #[test]
+    fn test_calls_text() {
+        let calls = [(0, 0, 5), (1, 0, 2), (1, 2, 5), (2, 3, 5)];
+        let text = calls_text(5, &calls, &[0, 2, 3], 2, &Theme::default());
+        let rows: Vec<String> = text
+            .lines
+            .iter()
+            .map(|line| {
+                line.spans
+                    .iter()
+                    .map(|span| span.content.as_ref())
+                    .collect()
+            })
+            .collect();
+        assert_eq!(vec!["├┤├─┤", "   ├┤"], rows);
+        assert_eq!(None, text.lines[0].spans[0].style.fg);
+        assert_eq!(Some(Color::LightCyan), text.lines[0].spans[1].style.fg);
+        assert_eq!(Some(Color::LightMagenta), text.lines[1].spans[1].style.fg);
+    }
+
+    #[test]
+    fn test_comparator_columns() {
+        let columns = comparator_columns(&[(0, 3), (1, 2), (4, 5)]);
+        assert_eq!(vec![vec![(0, 3), (4, 5)], vec![(1, 2)]], columns);
+    }
+
+    #[test]
+    fn test_wires_text() {
+        let layers: [&[(usize, usize)]; 2] = [&[(0, 2)], &[(0, 1)]];
+        let text = wires_text(3, &layers, Some(1), 80, &Theme::default());
+        let rows: Vec<String> = text
+            .lines
+            .iter()
+            .map(|line| {
+                line.spans
+                    .iter()
+                    .map(|span| span.content.as_ref())
+                    .collect()
+            })
+            .collect();
+        assert_eq!(vec!["─●─●─", "─┼─●─", "─●───"], rows);
+        assert_eq!(Some(Color::LightCyan), text.lines[0].spans[1].style.fg);
     }
 
     #[test]
     fn test_blocks_size() {
-        let (w, h) = blocks_size(Rect::new(0, 0, 64, 64)).unwrap();
-        assert!(w == 32);
-        assert!(h == 8);
+        assert_eq!((32, 8), blocks_size(32));
+        assert_eq!((33, 9), blocks_size(33));
     }
 
     #[test]
-    #[should_panic(expected = "width is too small")]
-    fn test_blocks_size_width_too_small() {
-        blocks_size(Rect::new(0, 0, 8, 64)).unwrap();
+    fn test_algorithm_ui_renderers() {
+        let algorithm = get_algorithms()[0];
+        let ui = AlgorithmUI::new(
+            algorithm,
+            Order::Ascending,
+            Rect::new(0, 0, 64, 64),
+            &config(32),
+            &Theme::default(),
+        );
+        assert_eq!("bars", ui.renderer().name());
+
+        // Hundreds of elements only fit as Braille.
+        let ui = AlgorithmUI::new(
+            algorithm,
+            Order::Ascending,
+            Rect::new(0, 0, 120, 40),
+            &config(236),
+            &Theme::default(),
+        );
+        let names: Vec<&str> = ui
+            .renderers
+            .iter()
+            .map(|renderer| renderer.name())
+            .collect();
+        assert_eq!(vec!["braille"], names);
+
+        // Charts made of colours are left out without them.
+        let ui = AlgorithmUI::new(
+            algorithm,
+            Order::Ascending,
+            Rect::new(0, 0, 64, 64),
+            &config(32),
+            &Theme::builtin("mono").unwrap(),
+        );
+        assert!(ui.renderers.iter().all(|renderer| renderer.name() != "hue"));
+
+        let mut dots = config(32);
+        dots.rendering.chart = "dots".to_string();
+        let ui = AlgorithmUI::new(
+            algorithm,
+            Order::Ascending,
+            Rect::new(0, 0, 64, 64),
+            &dots,
+            &Theme::default(),
+        );
+        assert_eq!("dots", ui.renderer().name());
     }
 
     #[test]
-    #[should_panic(expected = "height is too small")]
-    fn test_blocks_size_height_too_small() {
-        blocks_size(Rect::new(0, 0, 64, 4)).unwrap();
+    fn test_play_tiny_arrays() {
+        let size = Rect::new(0, 0, 80, 40);
+        let mut terminal = Terminal::new(backend::TestBackend::new(80, 40)).unwrap();
+        for n in [0, 1] {
+            for algorithm in get_algorithms() {
+                let mut app = App::new(
+                    vec![algorithm],
+                    Order::Ascending,
+                    config(n),
+                    Theme::default(),
+                );
+                let ui = AlgorithmUI::new(algorithm, app.order(), size, &app.config, &app.theme);
+                let status = ui.status.as_ref();
+                (algorithm.sort)(&mut status.nums.clone(), status.order, status);
+                app.algorithm = Some(ui);
+                let renderers = app.algorithm.as_ref().unwrap().renderers.len().max(1);
+                for view in 0..4 {
+                    for _ in 0..renderers {
+                        let ui = app.algorithm.as_mut().unwrap();
+                        (ui.wires, ui.tree, ui.calls) = (view == 1, view == 2, view == 3);
+                        let steps = ui.status.position().1;
+                        for step in 0..steps {
+                            app.algorithm.as_ref().unwrap().status.seek(step);
+                            terminal
+                                .draw(|frame| render_screen(frame, &mut app))
+                                .unwrap();
+                        }
+                        let ui = app.algorithm.as_mut().unwrap();
+                        ui.inspect(1, 1);
+                        ui.next_renderer();
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_theme_name() {
+        assert_eq!("light", theme_name(Some("light".to_string())));
+        if env::var_os("NO_COLOR").is_none() {
+            assert_eq!("dark", theme_name(None));
+        }
+    }
+
+    #[test]
+    fn test_cli_apply() {
+        let cli = Cli::parse_from([
+            "sorting-visualizer",
+            "--size",
+            "64",
+            "--paused",
+            "-o",
+            "mod:3",
+        ]);
+        let mut config = Config::default();
+        cli.apply(&mut config);
+        assert_eq!(64, config.input.size);
+        assert_eq!("mod:3", config.input.order);
+        assert!(!config.playback.autoplay);
+        // Settings not given keep the value of the config file.
+        assert!(config.audio.muted);
+        assert!(Cli::try_parse_from(["sorting-visualizer", "-o", "sideways"]).is_err());
+        let cli = Cli::parse_from(["sorting-visualizer", "-n", "0"]);
+        cli.apply(&mut config);
+        assert_eq!(0, config.input.size);
+        assert!(Cli::try_parse_from(["sorting-visualizer", "-n", "512"]).is_ok());
+        assert!(Cli::try_parse_from(["sorting-visualizer", "-n", "513"]).is_err());
+    }
+
+    #[test]
+    fn test_algorithms() {
+        let mut config = config(32);
+        let plugin =
+            PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("examples/plugins/bubble_sort.sh");
+        config.plugins.paths.push(plugin);
+        let loaded = algorithms(&config).unwrap();
+        assert_eq!(get_algorithms().len() + 1, loaded.len());
+        assert_eq!("bubble_sort", loaded.last().unwrap().name);
+
+        config.plugins.paths.push(config.plugins.paths[0].clone());
+        let error = algorithms(&config).unwrap_err();
+        assert!(error
+            .to_string()
+            .ends_with("has the name of another algorithm"));
+    }
+
+    #[test]
+    fn test_shuffled() {
+        assert_eq!(shuffled(16, Some(3)), shuffled(16, Some(3)));
+        let mut nums = shuffled(16, None);
+        nums.sort();
+        assert_eq!(init_vec(16), nums);
+    }
+
+    #[test]
+    fn test_algorithm_ui_resize() {
+        let algorithm = get_algorithms()[0];
+        let mut dots = config(32);
+        dots.rendering.chart = "dots".to_string();
+        let mut ui = AlgorithmUI::new(
+            algorithm,
+            Order::Ascending,
+            Rect::new(0, 0, 8, 4),
+            &dots,
+            &Theme::default(),
+        );
+        assert!(ui.renderers.is_empty());
+        // Braille fits 32 elements in 16 by 1 cells.
+        assert_eq!((18, 7), ui.min_size());
+        ui.next_renderer();
+        ui.inspect(1, 1);
+
+        ui.resize(Rect::new(0, 0, 64, 64));
+        assert_eq!("bars", ui.renderer().name());
+        ui.next_renderer();
+        let chart = ui.renderer().name();
+        ui.resize(Rect::new(0, 0, 120, 40));
+        assert_eq!(chart, ui.renderer().name());
+    }
+
+    #[test]
+    fn test_update_preview() {
+        let mut app = App::new(
+            get_algorithms(),
+            Order::Ascending,
+            Config::default(),
+            Theme::default(),
+        );
+        app.update_preview();
+        assert!(app.preview.is_none());
+
+        app.list.next();
+        app.update_preview();
+        let preview = app.preview.as_ref().unwrap();
+        assert_eq!(app.list.items[0].name, preview.algorithm.name);
+        assert_eq!(app.preview_nums, preview.steps[0].nums);
+
+        app.next_order();
+        app.update_preview();
+        let step = app.preview.as_ref().unwrap().steps.last().unwrap();
+        assert!(Order::Descending.is_sorted(&step.nums));
+
+        // A plugin that never answers does not hold up the menu.
+        let mut config = Config::default();
+        config.plugins.paths =
+            vec![PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/plugins/silent.sh")];
+        let silent = plugin::load(&config.plugins.paths[0], config.limits()).unwrap();
+        let mut app = App::new(vec![silent], Order::Ascending, config, Theme::default());
+        app.list.next();
+        app.update_preview();
+        assert_eq!(1, app.preview.as_ref().unwrap().steps.len());
+
+        // Closing the player stops the run behind it.
+        let size = Rect::new(0, 0, 80, 40);
+        start_algorithm(&mut app, 0, size);
+        let status = app.algorithm.as_ref().unwrap().status.clone();
+        app.algorithm = None;
+        assert!(status.cancelled());
+    }
+
+    #[test]
+    fn test_handle_key_events() {
+        let mut app = App::new(
+            get_algorithms(),
+            Order::Ascending,
+            Config::default(),
+            Theme::default(),
+        );
+        let size = Rect::new(0, 0, 80, 40);
+        let press = |c| KeyEvent::from(crossterm::event::KeyCode::Char(c));
+        handle_key_events(press('j'), &mut app, size);
+        assert_eq!(Some(0), app.list.state.selected());
+
+        handle_key_events(press('?'), &mut app, size);
+        assert!(app.help);
+        // The key closing the help does nothing else.
+        handle_key_events(press('j'), &mut app, size);
+        assert!(!app.help);
+        assert_eq!(Some(0), app.list.state.selected());
+
+        let ctrl_c = KeyEvent::new(
+            crossterm::event::KeyCode::Char('c'),
+            crossterm::event::KeyModifiers::CONTROL,
+        );
+        assert!(matches!(
+            handle_key_events(ctrl_c, &mut app, size),
+            Flow::Quit
+        ));
+    }
+
+    #[test]
+    fn test_timeline() {
+        assert_eq!(Line::from("●────"), timeline_line(0, 9, 5));
+        assert_eq!(Line::from("━━●──"), timeline_line(4, 9, 5));
+        assert_eq!(Line::from("━━━━●"), timeline_line(8, 9, 5));
+        assert_eq!(Line::from("●──"), timeline_line(0, 1, 3));
+
+        assert_eq!(0, timeline_index(0, 9, 5));
+        assert_eq!(4, timeline_index(2, 9, 5));
+        assert_eq!(8, timeline_index(9, 9, 5));
+        assert_eq!(0, timeline_index(3, 1, 5));
+    }
+
+    #[test]
+    fn test_handle_mouse_events() {
+        let mut app = App::new(
+            get_algorithms(),
+            Order::Ascending,
+            config(32),
+            Theme::default(),
+        );
+        let size = Rect::new(0, 0, 80, 40);
+        app.list_area = Rect::new(0, 0, 34, 18);
+        let mouse = |kind, column, row| MouseEvent {
+            kind,
+            column,
+            row,
+            modifiers: crossterm::event::KeyModifiers::NONE,
+        };
+        let click = |column, row| mouse(MouseEventKind::Down(MouseButton::Left), column, row);
+
+        handle_mouse_events(mouse(MouseEventKind::ScrollDown, 5, 5), &mut app, size);
+        assert_eq!(Some(0), app.list.state.selected());
+        // The second line of the third item.
+        handle_mouse_events(click(5, 6), &mut app, size);
+        assert_eq!(Some(2), app.list.state.selected());
+        // Clicks on the border miss.
+        handle_mouse_events(click(0, 6), &mut app, size);
+        assert_eq!(Some(2), app.list.state.selected());
+        handle_mouse_events(click(5, 6), &mut app, size);
+        let algorithm = app.algorithm.as_ref().unwrap();
+        assert_eq!(app.list.items[2].name, algorithm.status.algorithm.name);
+
+        // A player without a sort running in the background records only these steps.
+        let algorithm = AlgorithmUI::new(
+            app.list.items[2],
+            Order::Ascending,
+            size,
+            &app.config,
+            &app.theme,
+        );
+        let algorithm = app.algorithm.insert(algorithm);
+        for _ in 0..9 {
+            algorithm.status.next(Operation::Noop(), init_vec(32));
+        }
+        algorithm.timeline_area = Rect::new(10, 30, 11, 1);
+        // The rest of the footer row is not the timeline.
+        handle_mouse_events(click(30, 30), &mut app, size);
+        assert_eq!((0, 10), app.algorithm.as_ref().unwrap().status.position());
+        handle_mouse_events(click(15, 30), &mut app, size);
+        let algorithm = app.algorithm.as_mut().unwrap();
+        assert_eq!((5, 10), algorithm.status.position());
+        assert!(!algorithm.auto_next);
+        let drag = mouse(MouseEventKind::Drag(MouseButton::Left), 40, 30);
+        handle_mouse_events(drag, &mut app, size);
+        let algorithm = app.algorithm.as_mut().unwrap();
+        assert_eq!((9, 10), algorithm.status.position());
+
+        algorithm.chart_area = Rect::new(20, 5, 32, 8);
+        handle_mouse_events(click(23, 10), &mut app, size);
+        let algorithm = app.algorithm.as_ref().unwrap();
+        assert_eq!(Some(3), algorithm.inspected);
+        assert!(algorithm
+            .inspect_info()
+            .unwrap()
+            .starts_with("index: 3 value: "));
     }
 
     #[test]
@@ -545,4 +2127,18 @@ mod tests {
         assert_eq!(area.width, 32);
         assert_eq!(area.height, 8);
     }
+
+    #[test]
+    fn test_render_too_small() {
+        let mut terminal = Terminal::new(backend::TestBackend::new(24, 6)).unwrap();
+        terminal
+            .draw(|frame| render_too_small(frame, 40, 12))
+            .unwrap();
+        let buffer = terminal.backend().buffer();
+        let row = |y| -> String {
+            return (0..24).map(|x| buffer.get(x, y).symbol()).collect();
+        };
+        assert_eq!("   terminal too small   ", row(2));
+        assert_eq!("  need 40x12, have 24x6 ", row(3));
+    }
 }
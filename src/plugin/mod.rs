@@ -0,0 +1,277 @@
+//! Sorting algorithms loaded at startup from outside the program, shown in the
+//! menu alongside the built-in ones.
+
+use std::{fs, path::Path, time::Duration};
+
+use anyhow::{anyhow, Context, Result};
+
+use crate::sorting::{
+    Algorithm, AlgorithmContext, Complexity, Family, Operation,
+    Operation::{Compare, Noop, Swap, Write},
+    OperationKind, Order, Sort, Step,
+};
+
+pub mod process;
+pub mod script;
+pub mod wasm;
+
+/// Limits on a plugin, to stop those that never finish or take all the memory.
+#[derive(Debug, Clone, Copy)]
+pub struct Limits {
+    /// Compares, swaps and writes a plugin may make before it is stopped.
+    pub max_steps: u64,
+    /// Bytes of memory a WebAssembly plugin may grow to.
+    pub max_memory: usize,
+    /// Time an executable plugin has to finish, counted from its start.
+    pub timeout: Duration,
+}
+
+impl Default for Limits {
+    fn default() -> Self {
+        return Limits {
+            max_steps: 1_000_000,
+            max_memory: 16 << 20,
+            timeout: Duration::from_secs(10),
+        };
+    }
+}
+
+/// The array of a plugin running inside the visualizer and the steps it made. Host
+/// functions outlive a call into the plugin, so they record the steps here and
+/// `finish` reports them once it stops.
+struct Run {
+    nums: Vec<i32>,
+    /// The elements given to the plugin, sorted, the only values it may write.
+    elements: Vec<i32>,
+    order: Order,
+    steps: Vec<Step>,
+    max_steps: u64,
+}
+
+impl Run {
+    fn new(nums: &[i32], order: Order, limits: Limits) -> Run {
+        let mut elements = nums.to_vec();
+        elements.sort();
+        return Run {
+            nums: nums.to_vec(),
+            elements,
+            order,
+            steps: Vec::new(),
+            max_steps: limits.max_steps,
+        };
+    }
+
+    fn index(&self, i: i64) -> Result<usize, String> {
+        return match usize::try_from(i) {
+            Ok(i) if i < self.nums.len() => Ok(i),
+            _ => Err(format!(
+                "index {} out of bounds of {} elements",
+                i,
+                self.nums.len()
+            )),
+        };
+    }
+
+    fn step(&mut self, operation: Operation) -> Result<(), String> {
+        if self.steps.len() as u64 >= self.max_steps {
+            return Err(format!("plugin made more than {} steps", self.max_steps));
+        }
+        self.steps.push(Step::new(operation, self.nums.clone()));
+        return Ok(());
+    }
+
+    fn read(&self, i: i64) -> Result<i32, String> {
+        return Ok(self.nums[self.index(i)?]);
+    }
+
+    /// Returns `-1`, `0` or `1` as element `i` comes before, ties with or comes after
+    /// element `j`.
+    fn compare(&mut self, i: i64, j: i64) -> Result<i32, String> {
+        let (i, j) = (self.index(i)?, self.index(j)?);
+        if i == j {
+            return Err(format!("plugin compared element {} with itself", i));
+        }
+        self.step(Compare(i, j))?;
+        return Ok(self.order.compare(self.nums[i], self.nums[j]) as i32);
+    }
+
+    fn swap(&mut self, i: i64, j: i64) -> Result<(), String> {
+        let (i, j) = (self.index(i)?, self.index(j)?);
+        self.nums.swap(i, j);
+        return self.step(Swap(i, j));
+    }
+
+    fn write(&mut self, i: i64, value: i32) -> Result<(), String> {
+        let i = self.index(i)?;
+        // The renderers take values for heights, so one from outside the array
+        // cannot even be shown.
+        if self.elements.binary_search(&value).is_err() {
+            return Err(format!(
+                "plugin wrote {}, not an element of the array",
+                value
+            ));
+        }
+        self.nums[i] = value;
+        return self.step(Write(i));
+    }
+
+    /// Checks that the plugin sorted the same elements it was given, then reports
+    /// the steps made, leaving `nums` as the plugin did. The steps of a plugin that
+    /// fails are not reported, and `nums` is left as it was.
+    fn finish(
+        self,
+        nums: &mut [i32],
+        result: Result<()>,
+        ctx: &dyn AlgorithmContext,
+    ) -> Result<()> {
+        result?;
+        if !self.order.is_sorted(&self.nums) {
+            return Err(anyhow!("plugin finished with the array unsorted"));
+        }
+        let mut output = self.nums.clone();
+        output.sort();
+        if self.elements != output {
+            return Err(anyhow!("plugin changed the elements of the array"));
+        }
+        for step in self.steps {
+            ctx.next_step(step);
+        }
+        nums.copy_from_slice(&self.nums);
+        ctx.next(Noop(), self.nums);
+        return Ok(());
+    }
+}
+
+/// Loads the plugin at `path` as an algorithm named after the file: a Rhai script if
+/// it ends in `.rhai`, a WebAssembly module if it ends in `.wasm`, an executable
+/// otherwise.
+pub fn load(path: &Path, limits: Limits) -> Result<&'static Algorithm> {
+    if !path.is_file() {
+        return Err(anyhow!("plugin not found: {}", path.display()));
+    }
+    let name = path
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .ok_or_else(|| anyhow!("invalid plugin name: {}", path.display()))?;
+    let read = || fs::read(path).with_context(|| format!("failed to read {}", path.display()));
+    let (description, operations, sort) = match path.extension().and_then(|ext| ext.to_str()) {
+        Some("rhai") => {
+            let source = String::from_utf8(read()?)?;
+            let ast = script::compile(&source)
+                .with_context(|| format!("invalid script {}", path.display()))?;
+            (
+                format!("Runs the script {}.", path.display()),
+                SWAPS,
+                reporting(move |nums, order, ctx| script::sort(&ast, nums, order, limits, ctx)),
+            )
+        }
+        Some("wasm") => {
+            let module = wasm::compile(&read()?)
+                .with_context(|| format!("invalid module {}", path.display()))?;
+            (
+                format!(
+                    "Runs the WebAssembly module {} in a sandbox.",
+                    path.display()
+                ),
+                WRITES,
+                reporting(move |nums, order, ctx| wasm::sort(&module, nums, order, limits, ctx)),
+            )
+        }
+        _ => {
+            let path = path.to_path_buf();
+            (
+                format!(
+                    "Runs {} and compares and swaps the elements it asks for.",
+                    path.display()
+                ),
+                SWAPS,
+                reporting(move |nums, order, ctx| process::sort(&path, nums, order, limits, ctx)),
+            )
+        }
+    };
+    return Ok(leak(name, description, operations, sort));
+}
+
+const SWAPS: &[OperationKind] = &[OperationKind::Compare, OperationKind::Swap];
+const WRITES: &[OperationKind] = &[
+    OperationKind::Compare,
+    OperationKind::Swap,
+    OperationKind::Write,
+];
+
+/// Reports a plugin that fails to the context, as sorts have no caller to return
+/// errors to.
+fn reporting<F>(sort: F) -> Box<Sort>
+where
+    F: Fn(&mut [i32], Order, &dyn AlgorithmContext) -> Result<()> + Sync + 'static,
+{
+    return Box::new(
+        move |nums: &mut [i32], order: Order, ctx: &dyn AlgorithmContext| {
+            if let Err(err) = sort(nums, order, ctx) {
+                ctx.fail(format!("{:#}", err));
+            }
+        },
+    );
+}
+
+/// Plugins are loaded once and live as long as the program, like the built-in
+/// algorithms.
+fn leak(
+    name: &str,
+    description: String,
+    operations: &'static [OperationKind],
+    sort: Box<Sort>,
+) -> &'static Algorithm {
+    return Box::leak(Box::new(Algorithm {
+        name: name.to_string().leak(),
+        family: Family::Plugin,
+        stable: false,
+        in_place: true,
+        memory: "?",
+        complexity: Complexity {
+            best: "?",
+            average: "?",
+            worst: "?",
+        },
+        description: description.leak(),
+        operations,
+        sort: Box::leak(sort),
+    }));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sorting::{record, Operation};
+
+    #[test]
+    fn test_load() {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("examples/plugins/bubble_sort.sh");
+        let algorithm = load(&path, Limits::default()).unwrap();
+        assert_eq!("bubble_sort", algorithm.name);
+        assert_eq!(Family::Plugin, algorithm.family);
+        let steps = record(algorithm, &[3, 1, 2], Order::Descending);
+        assert_eq!(vec![3, 2, 1], steps.last().unwrap().nums);
+        assert!(steps
+            .iter()
+            .any(|step| matches!(step.operation, Operation::Swap(1, 2))));
+
+        let path =
+            Path::new(env!("CARGO_MANIFEST_DIR")).join("examples/plugins/selection_sort.rhai");
+        let algorithm = load(&path, Limits::default()).unwrap();
+        assert_eq!("selection_sort", algorithm.name);
+        let steps = record(algorithm, &[3, 1, 2], Order::Ascending);
+        assert_eq!(vec![1, 2, 3], steps.last().unwrap().nums);
+
+        let source = include_str!("../../examples/plugins/insertion_sort.wat");
+        let path = std::env::temp_dir().join("sorting-visualizer-insertion_sort.wasm");
+        fs::write(&path, wat::parse_str(source).unwrap()).unwrap();
+        let algorithm = load(&path, Limits::default()).unwrap();
+        assert_eq!("sorting-visualizer-insertion_sort", algorithm.name);
+        assert!(algorithm.operations.contains(&OperationKind::Write));
+        let steps = record(algorithm, &[3, 1, 2], Order::Ascending);
+        assert_eq!(vec![1, 2, 3], steps.last().unwrap().nums);
+
+        assert!(load(Path::new("missing.sh"), Limits::default()).is_err());
+    }
+}
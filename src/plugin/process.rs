@@ -0,0 +1,315 @@
+//! Plugins that run as a separate executable, written in any language, and talk to
+//! the visualizer one line at a time.
+//!
+//! The visualizer writes the number of elements followed by the elements to the
+//! plugin's stdin, e.g. `3 30 10 20`, and owns the array from then on. The plugin
+//! replies on stdout with requests:
+//!
+//! - `compare <i> <j>`: the visualizer answers `-1`, `0` or `1` as element `i`
+//!   comes before, ties with or comes after element `j` in the sort order.
+//! - `swap <i> <j>`: swaps two elements, with no answer.
+//! - `done`: the array is sorted.
+//!
+//! Anything written to stderr is kept for the error message if the plugin fails.
+
+use std::{
+    io::{BufRead, BufReader, Read, Write},
+    path::Path,
+    process::{Child, Command, ExitStatus, Stdio},
+    sync::mpsc::{self, RecvTimeoutError},
+    thread,
+    time::{Duration, Instant},
+};
+
+use anyhow::{anyhow, Context, Result};
+
+use super::{Limits, Run};
+use crate::sorting::{AlgorithmContext, Order};
+
+/// How often a plugin that has nothing to say is checked for its deadline.
+const POLL: Duration = Duration::from_millis(50);
+
+/// Sorts `nums` with the plugin at `path`, checking every request it makes, then
+/// reports its steps.
+pub fn sort(
+    path: &Path,
+    nums: &mut [i32],
+    order: Order,
+    limits: Limits,
+    ctx: &dyn AlgorithmContext,
+) -> Result<()> {
+    let mut child = Command::new(path)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .with_context(|| format!("failed to run {}", path.display()))?;
+    let deadline = Deadline {
+        at: Instant::now() + limits.timeout,
+        timeout: limits.timeout,
+    };
+    // Read stderr as it comes so that a chatty plugin does not block on it.
+    let mut stderr = child.stderr.take().unwrap();
+    let (errors, received) = mpsc::channel();
+    thread::spawn(move || {
+        let mut text = String::new();
+        let _ = stderr.read_to_string(&mut text);
+        let _ = errors.send(text);
+    });
+
+    let mut run = Run::new(nums, order, limits);
+    let result = match talk(&mut child, nums, &mut run, &deadline, ctx) {
+        Ok(true) => Ok(()),
+        Ok(false) => wait(&mut child, &deadline, ctx)
+            .and_then(|status| Err(anyhow!("plugin exited with {} before it was done", status))),
+        Err(err) => Err(err),
+    };
+    // Done, broken or out of time, either way the plugin has nothing more to say.
+    let _ = child.kill();
+    let _ = child.wait();
+    // Processes the plugin started may still hold stderr open.
+    let errors = received.recv_timeout(POLL * 10).unwrap_or_default();
+    return run.finish(nums, result, ctx).map_err(|err| {
+        return match errors.lines().rev().find(|line| !line.trim().is_empty()) {
+            Some(line) => err.context(line.trim().to_string()),
+            None => err,
+        };
+    });
+}
+
+/// When a plugin has to be done by.
+struct Deadline {
+    at: Instant,
+    timeout: Duration,
+}
+
+impl Deadline {
+    /// How long to wait for the plugin before checking again, or why it must stop.
+    fn wait_for(&self, ctx: &dyn AlgorithmContext) -> Result<Duration> {
+        if ctx.cancelled() {
+            return Err(anyhow!("plugin was stopped"));
+        }
+        let left = self.at.saturating_duration_since(Instant::now());
+        if left.is_zero() {
+            return Err(anyhow!("plugin did not finish within {:?}", self.timeout));
+        }
+        return Ok(left.min(POLL));
+    }
+}
+
+/// Sends the array and answers requests until the plugin is done, returning
+/// whether it said so before closing stdout.
+fn talk(
+    child: &mut Child,
+    nums: &[i32],
+    run: &mut Run,
+    deadline: &Deadline,
+    ctx: &dyn AlgorithmContext,
+) -> Result<bool> {
+    // Lines are written on another thread too, so that a plugin that does not read
+    // them cannot block past its deadline once the pipe is full. It stops when the
+    // plugin is killed.
+    let mut input = child.stdin.take().unwrap();
+    let (answers, to_write) = mpsc::channel::<String>();
+    thread::spawn(move || {
+        for line in to_write {
+            if writeln!(input, "{}", line)
+                .and_then(|_| input.flush())
+                .is_err()
+            {
+                return;
+            }
+        }
+    });
+    let values: Vec<String> = nums.iter().map(|value| value.to_string()).collect();
+    let _ = answers.send(format!("{} {}", nums.len(), values.join(" ")));
+
+    // Lines are read on another thread so that a plugin that says nothing does not
+    // block past its deadline.
+    let output = BufReader::new(child.stdout.take().unwrap());
+    let (lines, received) = mpsc::channel();
+    thread::spawn(move || {
+        for line in output.lines() {
+            if lines.send(line).is_err() {
+                return;
+            }
+        }
+    });
+
+    loop {
+        let line = match received.recv_timeout(deadline.wait_for(ctx)?) {
+            Ok(line) => line?,
+            Err(RecvTimeoutError::Timeout) => continue,
+            Err(RecvTimeoutError::Disconnected) => return Ok(false),
+        };
+        let request: Vec<&str> = line.split_whitespace().collect();
+        match request.as_slice() {
+            [] => continue,
+            ["compare", i, j] => {
+                let answer = run
+                    .compare(index(i)?, index(j)?)
+                    .map_err(|err| anyhow!(err))?;
+                let _ = answers.send(answer.to_string());
+            }
+            ["swap", i, j] => run.swap(index(i)?, index(j)?).map_err(|err| anyhow!(err))?,
+            ["done"] => return Ok(true),
+            _ => return Err(anyhow!("unknown request '{}'", line)),
+        }
+    }
+}
+
+/// Waits for a plugin that closed stdout to exit.
+fn wait(child: &mut Child, deadline: &Deadline, ctx: &dyn AlgorithmContext) -> Result<ExitStatus> {
+    loop {
+        if let Some(status) = child.try_wait()? {
+            return Ok(status);
+        }
+        thread::sleep(deadline.wait_for(ctx)?);
+    }
+}
+
+/// Parses an index, leaving its bounds to `Run`.
+fn index(s: &str) -> Result<i64> {
+    return s.parse().map_err(|_| anyhow!("invalid index '{}'", s));
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{path::PathBuf, sync::Mutex};
+
+    use super::*;
+    use crate::{init_vec, sorting::Operation};
+
+    const LIMITS: Limits = Limits {
+        max_steps: 1000,
+        max_memory: 0,
+        timeout: Duration::from_secs(10),
+    };
+
+    struct Steps(Mutex<Vec<Operation>>);
+
+    impl AlgorithmContext for Steps {
+        fn next(&self, operation: Operation, _: Vec<i32>) {
+            self.0.lock().unwrap().push(operation);
+        }
+    }
+
+    fn plugin(name: &str) -> PathBuf {
+        return Path::new(env!("CARGO_MANIFEST_DIR")).join(name);
+    }
+
+    fn run(name: &str, nums: &mut [i32]) -> Result<Vec<Operation>> {
+        let steps = Steps(Mutex::new(vec![]));
+        sort(&plugin(name), nums, Order::Ascending, LIMITS, &steps)?;
+        return Ok(steps.0.into_inner().unwrap());
+    }
+
+    #[test]
+    fn test_sort() {
+        let nums = &mut [5, 3, 4, 1, 2];
+        let steps = run("examples/plugins/bubble_sort.sh", nums).unwrap();
+        assert_eq!([1, 2, 3, 4, 5], *nums);
+        assert!(matches!(steps[0], Operation::Compare(0, 1)));
+        assert!(matches!(steps[1], Operation::Swap(0, 1)));
+        assert!(matches!(steps.last(), Some(Operation::Noop())));
+
+        // An empty array is already sorted.
+        let steps = run("examples/plugins/bubble_sort.sh", &mut []).unwrap();
+        assert_eq!(1, steps.len());
+    }
+
+    #[test]
+    fn test_sort_errors() {
+        let error = |name| run(name, &mut [3, 1, 2]).unwrap_err().to_string();
+        assert_eq!(
+            "index 99 out of bounds of 3 elements",
+            error("tests/plugins/out_of_bounds.sh")
+        );
+        assert_eq!(
+            "plugin finished with the array unsorted",
+            error("tests/plugins/unsorted.sh")
+        );
+        assert_eq!(
+            "plugin made more than 1000 steps",
+            error("tests/plugins/forever.sh")
+        );
+        // The last line of stderr explains the failure.
+        assert_eq!("out of ideas", error("tests/plugins/crash.sh"));
+        let crash = run("tests/plugins/crash.sh", &mut [3, 1, 2]).unwrap_err();
+        assert!(format!("{:#}", crash).ends_with("exit status: 3 before it was done"));
+        assert!(run("tests/plugins/missing.sh", &mut [3, 1, 2]).is_err());
+    }
+
+    struct Cancelled;
+
+    impl AlgorithmContext for Cancelled {
+        fn next(&self, _: Operation, _: Vec<i32>) {}
+
+        fn cancelled(&self) -> bool {
+            return true;
+        }
+    }
+
+    #[test]
+    fn test_sort_deadline() {
+        let limits = Limits {
+            timeout: Duration::from_millis(200),
+            ..LIMITS
+        };
+        // Plugins that never answer, or close stdout and keep running, are killed.
+        for name in ["tests/plugins/silent.sh", "tests/plugins/closed.sh"] {
+            let start = Instant::now();
+            let steps = Steps(Mutex::new(vec![]));
+            let err = sort(
+                &plugin(name),
+                &mut [3, 1, 2],
+                Order::Ascending,
+                limits,
+                &steps,
+            );
+            assert_eq!(
+                "plugin did not finish within 200ms",
+                err.unwrap_err().to_string()
+            );
+            assert!(start.elapsed() < Duration::from_secs(2));
+        }
+
+        // Plugins that never read stdin are killed too, whether the array or the
+        // answers fill the pipe first.
+        let path = plugin("tests/plugins/deaf.sh");
+        let start = Instant::now();
+        let steps = Steps(Mutex::new(vec![]));
+        let limits = Limits {
+            max_steps: u64::MAX,
+            ..limits
+        };
+        let err = sort(&path, &mut [3, 1, 2], Order::Ascending, limits, &steps);
+        assert_eq!(
+            "plugin did not finish within 200ms",
+            err.unwrap_err().to_string()
+        );
+        let limits = Limits {
+            max_steps: 10,
+            ..LIMITS
+        };
+        let err = sort(
+            &path,
+            &mut init_vec(20_000),
+            Order::Ascending,
+            limits,
+            &steps,
+        );
+        assert_eq!(
+            "plugin made more than 10 steps",
+            err.unwrap_err().to_string()
+        );
+        assert!(start.elapsed() < Duration::from_secs(4));
+
+        // A run nobody waits for any more stops early.
+        let start = Instant::now();
+        let path = plugin("tests/plugins/silent.sh");
+        let err = sort(&path, &mut [3, 1, 2], Order::Ascending, LIMITS, &Cancelled);
+        assert_eq!("plugin was stopped", err.unwrap_err().to_string());
+        assert!(start.elapsed() < Duration::from_secs(2));
+    }
+}
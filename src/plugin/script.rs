@@ -0,0 +1,128 @@
+//! Plugins written as Rhai scripts, run inside the visualizer.
+//!
+//! A script sorts through the functions `len()`, `get(i)`, `compare(i, j)` and
+//! `swap(i, j)`, where `compare` returns `-1`, `0` or `1` as element `i` comes
+//! before, ties with or comes after element `j` in the sort order.
+
+use std::sync::{Arc, Mutex};
+
+use anyhow::{anyhow, Result};
+use rhai::{Engine, EvalAltResult, AST};
+
+use super::{Limits, Run};
+use crate::sorting::{AlgorithmContext, Order};
+
+/// Rhai operations allowed for every step, to stop loops that never compare or swap.
+const OPERATIONS_PER_STEP: u64 = 1000;
+
+pub fn compile(source: &str) -> Result<AST> {
+    return Ok(Engine::new().compile(source)?);
+}
+
+/// Sorts `nums` with the script, then reports its steps.
+pub fn sort(
+    ast: &AST,
+    nums: &mut [i32],
+    order: Order,
+    limits: Limits,
+    ctx: &dyn AlgorithmContext,
+) -> Result<()> {
+    let run = Arc::new(Mutex::new(Run::new(nums, order, limits)));
+    let result = engine(&run, limits)
+        .run_ast(ast)
+        .map_err(|err| anyhow!("{}", err));
+    let run = Arc::into_inner(run).unwrap().into_inner().unwrap();
+    return run.finish(nums, result, ctx);
+}
+
+fn engine(run: &Arc<Mutex<Run>>, limits: Limits) -> Engine {
+    let mut engine = Engine::new();
+    engine.set_max_operations(limits.max_steps.saturating_mul(OPERATIONS_PER_STEP));
+    // Printing would draw over the interface.
+    engine.on_print(|_| {});
+    engine.on_debug(|_, _, _| {});
+
+    let state = run.clone();
+    engine.register_fn("len", move || state.lock().unwrap().nums.len() as i64);
+    let state = run.clone();
+    engine.register_fn("get", move |i: i64| {
+        return Ok::<_, Box<EvalAltResult>>(state.lock().unwrap().read(i)? as i64);
+    });
+    let state = run.clone();
+    engine.register_fn("compare", move |i: i64, j: i64| {
+        return Ok::<_, Box<EvalAltResult>>(state.lock().unwrap().compare(i, j)? as i64);
+    });
+    let state = run.clone();
+    engine.register_fn("swap", move |i: i64, j: i64| {
+        return Ok::<_, Box<EvalAltResult>>(state.lock().unwrap().swap(i, j)?);
+    });
+    return engine;
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::sorting::{
+        Operation,
+        Operation::{Compare, Noop, Swap},
+    };
+
+    const LIMITS: Limits = Limits {
+        max_steps: 1000,
+        max_memory: 0,
+        timeout: Duration::from_secs(10),
+    };
+
+    struct Steps(Mutex<Vec<Operation>>);
+
+    impl AlgorithmContext for Steps {
+        fn next(&self, operation: Operation, _: Vec<i32>) {
+            self.0.lock().unwrap().push(operation);
+        }
+    }
+
+    fn run(source: &str, nums: &mut [i32]) -> Result<Vec<Operation>> {
+        let steps = Steps(Mutex::new(vec![]));
+        sort(&compile(source)?, nums, Order::Descending, LIMITS, &steps)?;
+        return Ok(steps.0.into_inner().unwrap());
+    }
+
+    #[test]
+    fn test_sort() {
+        let source = include_str!("../../examples/plugins/selection_sort.rhai");
+        let nums = &mut [2, 5, 3, 1, 4];
+        let steps = run(source, nums).unwrap();
+        assert_eq!([5, 4, 3, 2, 1], *nums);
+        assert!(matches!(steps[0], Compare(1, 0)));
+        assert!(steps.iter().any(|step| matches!(step, Swap(0, 1))));
+        assert!(matches!(steps.last(), Some(Noop())));
+        assert_eq!(1, run(source, &mut []).unwrap().len());
+
+        assert!(run("if get(0) < get(1) { swap(0, 1); }", &mut [1, 2]).is_ok());
+    }
+
+    #[test]
+    fn test_sort_errors() {
+        let error = |source| run(source, &mut [1, 3, 2]).unwrap_err().to_string();
+        assert_eq!(
+            "Runtime error: index 99 out of bounds of 3 elements (line 1, position 1)",
+            error("swap(0, 99);")
+        );
+        assert!(error("compare(-1, 0);").contains("index -1 out of bounds"));
+        assert_eq!("plugin finished with the array unsorted", error(""));
+        assert!(error("loop { swap(0, 1); }").contains("plugin made more than 1000 steps"));
+        assert!(error("loop {}").starts_with("Too many operations"));
+        assert!(compile("let = ;").is_err());
+
+        // Steps made before the error are not reported, and the array is left as
+        // it was.
+        let steps = Steps(Mutex::new(vec![]));
+        let ast = compile("swap(0, 1); swap(0, 9);").unwrap();
+        let nums = &mut [1, 3, 2];
+        assert!(sort(&ast, nums, Order::Ascending, LIMITS, &steps).is_err());
+        assert_eq!([1, 3, 2], *nums);
+        assert!(steps.0.lock().unwrap().is_empty());
+    }
+}
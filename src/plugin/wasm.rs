@@ -0,0 +1,252 @@
+//! Plugins compiled to WebAssembly, run inside the visualizer in a sandbox with no
+//! access to the system and with limits on their instructions and memory, for code
+//! that cannot be trusted.
+//!
+//! A module exports `sort()` and imports from `env`:
+//!
+//! - `len() -> i32`
+//! - `read(i: i32) -> i32` and `write(i: i32, value: i32)`
+//! - `compare(i: i32, j: i32) -> i32`, returning `-1`, `0` or `1` as element `i`
+//!   comes before, ties with or comes after element `j` in the sort order.
+//! - `swap(i: i32, j: i32)`
+//!
+//! Modules may import any subset of these.
+
+use anyhow::{anyhow, Result};
+use wasmi::{
+    core::TrapCode, Caller, Config, Engine, Error, Linker, Module, Store, StoreLimits,
+    StoreLimitsBuilder,
+};
+
+use super::{Limits, Run};
+use crate::sorting::{AlgorithmContext, Order};
+
+/// Fuel allowed for every step, to stop loops that never compare, swap or write.
+const FUEL_PER_STEP: u64 = 1000;
+
+struct State {
+    run: Run,
+    limits: StoreLimits,
+}
+
+pub fn compile(wasm: &[u8]) -> Result<Module> {
+    let mut config = Config::default();
+    config.consume_fuel(true);
+    return Ok(Module::new(&Engine::new(&config), wasm)?);
+}
+
+/// Sorts `nums` with the module, then reports its steps.
+pub fn sort(
+    module: &Module,
+    nums: &mut [i32],
+    order: Order,
+    limits: Limits,
+    ctx: &dyn AlgorithmContext,
+) -> Result<()> {
+    let state = State {
+        run: Run::new(nums, order, limits),
+        limits: StoreLimitsBuilder::new()
+            .memory_size(limits.max_memory)
+            .build(),
+    };
+    let mut store = Store::new(module.engine(), state);
+    store.limiter(|state| &mut state.limits);
+    let fuel = limits.max_steps.saturating_mul(FUEL_PER_STEP);
+    store.set_fuel(fuel).map_err(|err| anyhow!("{}", err))?;
+    let result = call(&mut store, module).map_err(|err| match err.as_trap_code() {
+        Some(TrapCode::OutOfFuel) => anyhow!("plugin ran out of its {} fuel", fuel),
+        _ => anyhow!("{}", err),
+    });
+    return store.into_data().run.finish(nums, result, ctx);
+}
+
+fn call(store: &mut Store<State>, module: &Module) -> Result<(), Error> {
+    let mut linker = Linker::new(module.engine());
+    linker.func_wrap("env", "len", |caller: Caller<'_, State>| {
+        return caller.data().run.nums.len() as i32;
+    })?;
+    linker.func_wrap("env", "read", |caller: Caller<'_, State>, i: i32| {
+        return caller.data().run.read(i.into()).map_err(Error::new);
+    })?;
+    linker.func_wrap(
+        "env",
+        "write",
+        |mut caller: Caller<'_, State>, i: i32, value: i32| {
+            return caller
+                .data_mut()
+                .run
+                .write(i.into(), value)
+                .map_err(Error::new);
+        },
+    )?;
+    linker.func_wrap(
+        "env",
+        "compare",
+        |mut caller: Caller<'_, State>, i: i32, j: i32| {
+            return caller
+                .data_mut()
+                .run
+                .compare(i.into(), j.into())
+                .map_err(Error::new);
+        },
+    )?;
+    linker.func_wrap(
+        "env",
+        "swap",
+        |mut caller: Caller<'_, State>, i: i32, j: i32| {
+            return caller
+                .data_mut()
+                .run
+                .swap(i.into(), j.into())
+                .map_err(Error::new);
+        },
+    )?;
+    let instance = linker
+        .instantiate(&mut *store, module)?
+        .start(&mut *store)?;
+    let sort = instance
+        .get_typed_func::<(), ()>(&*store, "sort")
+        .map_err(|_| Error::new("plugin does not export a function sort()"))?;
+    return sort.call(&mut *store, ());
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{sync::Mutex, time::Duration};
+
+    use super::*;
+    use crate::sorting::{
+        Operation,
+        Operation::{Compare, Noop, Swap, Write},
+    };
+
+    const LIMITS: Limits = Limits {
+        max_steps: 1000,
+        max_memory: 1 << 16,
+        timeout: Duration::from_secs(10),
+    };
+
+    struct Steps(Mutex<Vec<Operation>>);
+
+    impl AlgorithmContext for Steps {
+        fn next(&self, operation: Operation, _: Vec<i32>) {
+            self.0.lock().unwrap().push(operation);
+        }
+    }
+
+    fn run(source: &str, nums: &mut [i32]) -> Result<Vec<Operation>> {
+        let module = compile(&wat::parse_str(source)?)?;
+        let steps = Steps(Mutex::new(vec![]));
+        sort(&module, nums, Order::Ascending, LIMITS, &steps)?;
+        return Ok(steps.0.into_inner().unwrap());
+    }
+
+    /// A module that imports every host function and runs `body` as `sort`.
+    fn module(body: &str) -> String {
+        return format!(
+            r#"(module
+              (import "env" "len" (func $len (result i32)))
+              (import "env" "read" (func $read (param i32) (result i32)))
+              (import "env" "write" (func $write (param i32 i32)))
+              (import "env" "compare" (func $compare (param i32 i32) (result i32)))
+              (import "env" "swap" (func $swap (param i32 i32)))
+              (func (export "sort") {}))"#,
+            body
+        );
+    }
+
+    #[test]
+    fn test_sort() {
+        let source = include_str!("../../examples/plugins/insertion_sort.wat");
+        let nums = &mut [3, 1, 2];
+        let steps = run(source, nums).unwrap();
+        assert_eq!([1, 2, 3], *nums);
+        assert!(matches!(steps[0], Compare(0, 1)));
+        assert!(matches!(steps[1], Swap(0, 1)));
+        assert!(matches!(steps.last(), Some(Noop())));
+        assert_eq!(1, run(source, &mut []).unwrap().len());
+
+        // Swaps elements through reads and writes.
+        let swap = module(
+            "(local $a i32)
+            (local.set $a (call $read (i32.const 0)))
+            (call $write (i32.const 0) (call $read (i32.const 1)))
+            (call $write (i32.const 1) (local.get $a))",
+        );
+        let nums = &mut [2, 1];
+        let steps = run(&swap, nums).unwrap();
+        assert_eq!([1, 2], *nums);
+        assert!(matches!(steps[..], [Write(0), Write(1), Noop()]));
+    }
+
+    #[test]
+    fn test_sort_errors() {
+        let error = |source: &str| run(source, &mut [1, 3, 2]).unwrap_err().to_string();
+        assert_eq!(
+            "index 7 out of bounds of 3 elements",
+            error(&module("(call $swap (i32.const 0) (i32.const 7))"))
+        );
+        assert_eq!(
+            "index -1 out of bounds of 3 elements",
+            error(&module("(drop (call $read (i32.const -1)))"))
+        );
+        assert_eq!(
+            "plugin changed the elements of the array",
+            error(&module("(call $write (i32.const 1) (i32.const 2))"))
+        );
+        assert_eq!(
+            "plugin finished with the array unsorted",
+            error(&module(""))
+        );
+        assert_eq!(
+            "plugin made more than 1000 steps",
+            error(&module(
+                "(loop $l (call $swap (i32.const 0) (i32.const 1)) (br $l))"
+            ))
+        );
+        assert_eq!(
+            "plugin ran out of its 1000000 fuel",
+            error(&module("(loop $l (br $l))"))
+        );
+        // Memory past the limit cannot be had, whether declared or grown.
+        assert_eq!(
+            "out of bounds memory allocation",
+            error("(module (memory 2) (func (export \"sort\")))")
+        );
+        let grow = r#"(module (memory 1)
+            (func (export "sort")
+              (if (i32.ne (memory.grow (i32.const 1)) (i32.const -1)) (then unreachable))))"#;
+        assert!(run(grow, &mut [1, 2]).is_ok());
+        assert_eq!(
+            "plugin does not export a function sort()",
+            error("(module)")
+        );
+        assert!(compile(b"not wasm").is_err());
+    }
+
+    #[test]
+    fn test_sort_foreign_values() {
+        // Values the renderers cannot draw trap at once, and none of the steps
+        // before them reach the player.
+        for value in [0, i32::MAX] {
+            let module = compile(
+                &wat::parse_str(module(&format!(
+                    "(call $swap (i32.const 0) (i32.const 1))
+                    (call $write (i32.const 1) (i32.const {}))",
+                    value
+                )))
+                .unwrap(),
+            )
+            .unwrap();
+            let nums = &mut [3, 1, 2];
+            let steps = Steps(Mutex::new(vec![]));
+            let err = sort(&module, nums, Order::Ascending, LIMITS, &steps).unwrap_err();
+            assert_eq!(
+                format!("plugin wrote {}, not an element of the array", value),
+                err.to_string()
+            );
+            assert_eq!([3, 1, 2], *nums);
+            assert!(steps.0.into_inner().unwrap().is_empty());
+        }
+    }
+}
@@ -0,0 +1,734 @@
+//! Charts that draw the numbers of a step, one per view the user can switch between.
+
+use std::f64::consts::TAU;
+
+use ratatui::{
+    buffer::Buffer,
+    prelude::*,
+    widgets::{Block, Widget},
+};
+use sorting_visualizer::sorting::Order;
+
+use crate::theme::{Highlight, Theme};
+
+const BLOCK_FULL: char = '\u{2588}';
+const BLOCK_HALF_QUARTER: char = '\u{2586}';
+const BLOCK_HALF: char = '\u{2584}';
+const BLOCK_QUARTER: char = '\u{2582}';
+const BLOCK_UPPER_HALF: char = '\u{2580}';
+const DOT: char = '\u{2022}';
+const MARKER: char = '\u{25B2}';
+// Left blocks one eighth wide and up, indexed by the number of eighths.
+const BLOCK_EIGHTHS: [char; 8] = [
+    ' ', '\u{258F}', '\u{258E}', '\u{258D}', '\u{258C}', '\u{258B}', '\u{258A}', '\u{2589}',
+];
+
+/// The most elements a chart is drawn for, two per column of the Braille chart on a
+/// terminal 256 columns wide. Every step keeps a copy of the array, so larger ones
+/// would take all the memory long before they could be seen.
+pub const MAX_SIZE: u16 = 512;
+
+/// Draws the numbers `1..=n` of a step, each index marked as highlighted in the theme.
+pub trait Renderer {
+    fn name(&self) -> &'static str;
+
+    /// Width and height of the chart in cells.
+    fn size(&self) -> (u16, u16);
+
+    /// Whether the chart shows nothing without colours.
+    fn colored(&self) -> bool {
+        return false;
+    }
+
+    /// The index of the element drawn at `x`, `y` of the chart, the first of two
+    /// sharing a cell.
+    fn index_at(&self, nums: &[i32], x: usize, _y: usize) -> Option<usize> {
+        return (x < nums.len()).then_some(x);
+    }
+
+    /// Draws into `area`, which is at most the size of the chart.
+    fn render(
+        &self,
+        nums: &[i32],
+        highlights: &[Option<Highlight>],
+        theme: &Theme,
+        area: Rect,
+        buf: &mut Buffer,
+    );
+}
+
+/// Returns every view of `nums` sorted by `order`, vertical bars first. Charts that
+/// can shrink stay within `max_height` rows.
+pub fn renderers(nums: &[i32], order: Order, max_height: u16) -> Vec<Box<dyn Renderer>> {
+    let n = nums.len();
+    let (width, height) = (n as u16, n.div_ceil(4) as u16);
+    return vec![
+        Box::new(Bars { width, height }),
+        Box::new(Braille {
+            width: n.div_ceil(2) as u16,
+            height: (n.div_ceil(8) as u16).clamp(1, max_height.max(1)),
+        }),
+        Box::new(Dots { width, height }),
+        // A row of colour above the marks, even for four elements or fewer.
+        Box::new(HueStrip {
+            width,
+            height: height.max(2),
+        }),
+        Box::new(Disparity::new(nums, order, height)),
+        Box::new(HorizontalBars {
+            width: width / 2,
+            height: nums.len() as u16,
+        }),
+    ];
+}
+
+/// A widget drawing a step with a renderer, centred horizontally in its block.
+pub struct Chart<'a> {
+    renderer: &'a dyn Renderer,
+    nums: &'a [i32],
+    highlights: &'a [Option<Highlight>],
+    theme: &'a Theme,
+    block: Option<Block<'a>>,
+}
+
+impl<'a> Chart<'a> {
+    pub fn new(
+        renderer: &'a dyn Renderer,
+        nums: &'a [i32],
+        highlights: &'a [Option<Highlight>],
+        theme: &'a Theme,
+    ) -> Self {
+        return Chart {
+            renderer,
+            nums,
+            highlights,
+            theme,
+            block: None,
+        };
+    }
+
+    pub fn block(mut self, block: Block<'a>) -> Self {
+        self.block = Some(block);
+        return self;
+    }
+}
+
+impl Widget for Chart<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let area = render_block(self.block, area, buf);
+        let (width, height) = self.renderer.size();
+        let area = centered(area, width, height);
+        self.renderer
+            .render(self.nums, self.highlights, self.theme, area, buf);
+    }
+}
+
+/// A widget drawing vertical bars with gaps, such as the slots of a buffer, bottom
+/// aligned and centred horizontally in its block.
+pub struct Columns<'a> {
+    values: &'a [Option<i32>],
+    highlights: &'a [Option<Highlight>],
+    theme: &'a Theme,
+    block: Option<Block<'a>>,
+}
+
+impl<'a> Columns<'a> {
+    pub fn new(
+        values: &'a [Option<i32>],
+        highlights: &'a [Option<Highlight>],
+        theme: &'a Theme,
+    ) -> Self {
+        return Columns {
+            values,
+            highlights,
+            theme,
+            block: None,
+        };
+    }
+
+    pub fn block(mut self, block: Block<'a>) -> Self {
+        self.block = Some(block);
+        return self;
+    }
+}
+
+impl Widget for Columns<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let area = render_block(self.block, area, buf);
+        let area = centered(area, self.values.len() as u16, area.height);
+        draw_columns(
+            self.values.iter().copied(),
+            self.highlights,
+            self.theme,
+            area,
+            buf,
+        );
+    }
+}
+
+fn render_block(block: Option<Block>, area: Rect, buf: &mut Buffer) -> Rect {
+    let Some(block) = block else {
+        return area;
+    };
+    let inner = block.inner(area);
+    block.render(area, buf);
+    return inner;
+}
+
+/// The top of `area` at most `width` by `height`, centred horizontally.
+pub fn centered(area: Rect, width: u16, height: u16) -> Rect {
+    let width = width.min(area.width);
+    return Rect {
+        x: area.x + (area.width - width) / 2,
+        y: area.y,
+        width,
+        height: height.min(area.height),
+    };
+}
+
+/// Sets the cell at `x`, `y` within `area`, ignoring positions outside of it.
+fn set_cell(buf: &mut Buffer, area: Rect, x: usize, y: usize, c: char, style: Style) {
+    if x >= area.width as usize || y >= area.height as usize {
+        return;
+    }
+    buf.get_mut(area.x + x as u16, area.y + y as u16)
+        .set_char(c)
+        .set_style(style);
+}
+
+/// Sets a cell of an element drawn as `c` in the colour of its highlight, or as
+/// the glyph of the highlight when the theme has no colours.
+fn set_mark(
+    buf: &mut Buffer,
+    area: Rect,
+    (x, y): (usize, usize),
+    c: char,
+    highlight: Option<Highlight>,
+    theme: &Theme,
+) {
+    let Some(highlight) = highlight else {
+        set_cell(buf, area, x, y, c, Style::default());
+        return;
+    };
+    let style = match theme.color(highlight) {
+        Some(color) => Style::default().fg(color),
+        None => Style::default(),
+    };
+    set_cell(buf, area, x, y, theme.glyph(highlight).unwrap_or(c), style);
+}
+
+/// Draws one bar per value from the bottom of `area` up, a quarter of a row per
+/// unit. `None` leaves the column empty.
+fn draw_columns(
+    values: impl Iterator<Item = Option<i32>>,
+    highlights: &[Option<Highlight>],
+    theme: &Theme,
+    area: Rect,
+    buf: &mut Buffer,
+) {
+    let height = area.height as usize;
+    for (x, value) in values.enumerate() {
+        let Some(value) = value else {
+            continue;
+        };
+        for row in 0..height {
+            let quarters = value - 4 * row as i32;
+            if quarters <= 0 {
+                break;
+            }
+            let c = match quarters {
+                1 => BLOCK_QUARTER,
+                2 => BLOCK_HALF,
+                3 => BLOCK_HALF_QUARTER,
+                _ => BLOCK_FULL,
+            };
+            set_mark(buf, area, (x, height - 1 - row), c, highlights[x], theme);
+        }
+    }
+}
+
+/// Vertical bars made of quarter blocks.
+struct Bars {
+    width: u16,
+    height: u16,
+}
+
+impl Renderer for Bars {
+    fn name(&self) -> &'static str {
+        return "bars";
+    }
+
+    fn size(&self) -> (u16, u16) {
+        return (self.width, self.height);
+    }
+
+    fn render(
+        &self,
+        nums: &[i32],
+        highlights: &[Option<Highlight>],
+        theme: &Theme,
+        area: Rect,
+        buf: &mut Buffer,
+    ) {
+        draw_columns(
+            nums.iter().map(|&num| Some(num)),
+            highlights,
+            theme,
+            area,
+            buf,
+        );
+    }
+}
+
+/// Vertical bars drawn with Braille patterns, two bars per cell and four dots
+/// per row, for arrays too large for one column per element.
+struct Braille {
+    width: u16,
+    height: u16,
+}
+
+// Bits of the dots of a Braille cell from the top, in the left and right column.
+const BRAILLE_DOTS: [[u32; 4]; 2] = [[0x01, 0x02, 0x04, 0x40], [0x08, 0x10, 0x20, 0x80]];
+
+impl Renderer for Braille {
+    fn name(&self) -> &'static str {
+        return "braille";
+    }
+
+    fn size(&self) -> (u16, u16) {
+        return (self.width, self.height);
+    }
+
+    fn index_at(&self, nums: &[i32], x: usize, _y: usize) -> Option<usize> {
+        return (2 * x < nums.len()).then_some(2 * x);
+    }
+
+    fn render(
+        &self,
+        nums: &[i32],
+        highlights: &[Option<Highlight>],
+        theme: &Theme,
+        area: Rect,
+        buf: &mut Buffer,
+    ) {
+        let height = self.height as usize;
+        let levels = 4 * height;
+        let mut cells = vec![vec![(0, None); self.width as usize]; height];
+        for (i, &num) in nums.iter().enumerate() {
+            let dots = (num as usize * levels).div_ceil(nums.len());
+            for level in 0..dots {
+                let cell = &mut cells[height - 1 - level / 4][i / 2];
+                cell.0 |= BRAILLE_DOTS[i % 2][3 - level % 4];
+                cell.1 = cell.1.or(highlights[i]);
+            }
+        }
+        for (y, row) in cells.iter().enumerate() {
+            for (x, &(bits, highlight)) in row.iter().enumerate() {
+                if bits != 0 {
+                    let c = char::from_u32(0x2800 + bits).unwrap();
+                    set_mark(buf, area, (x, y), c, highlight, theme);
+                }
+            }
+        }
+    }
+}
+
+/// A scatter plot of value against index, two values per cell.
+struct Dots {
+    width: u16,
+    height: u16,
+}
+
+impl Renderer for Dots {
+    fn name(&self) -> &'static str {
+        return "dots";
+    }
+
+    fn size(&self) -> (u16, u16) {
+        return (self.width, self.height);
+    }
+
+    fn render(
+        &self,
+        nums: &[i32],
+        highlights: &[Option<Highlight>],
+        theme: &Theme,
+        area: Rect,
+        buf: &mut Buffer,
+    ) {
+        let height = self.height as usize;
+        for (i, &num) in nums.iter().enumerate() {
+            let level = (num as usize - 1) * 2 * height / nums.len();
+            let c = [BLOCK_HALF, BLOCK_UPPER_HALF][level % 2];
+            set_mark(
+                buf,
+                area,
+                (i, height - 1 - level / 2),
+                c,
+                highlights[i],
+                theme,
+            );
+        }
+    }
+}
+
+/// Columns coloured along the hue circle by value, with the highlighted indices
+/// marked below.
+struct HueStrip {
+    width: u16,
+    height: u16,
+}
+
+impl Renderer for HueStrip {
+    fn name(&self) -> &'static str {
+        return "hue";
+    }
+
+    fn size(&self) -> (u16, u16) {
+        return (self.width, self.height);
+    }
+
+    fn colored(&self) -> bool {
+        return true;
+    }
+
+    fn render(
+        &self,
+        nums: &[i32],
+        highlights: &[Option<Highlight>],
+        theme: &Theme,
+        area: Rect,
+        buf: &mut Buffer,
+    ) {
+        let height = self.height as usize;
+        // The smallest and largest values take the two ends of the hues, however few.
+        let last = nums.len().saturating_sub(1).max(1) as f64;
+        for (i, &num) in nums.iter().enumerate() {
+            let color = hue_color((num - 1) as f64 / last);
+            for y in 0..height - 1 {
+                set_cell(buf, area, i, y, BLOCK_FULL, Style::default().fg(color));
+            }
+            if highlights[i].is_some() {
+                set_mark(buf, area, (i, height - 1), MARKER, highlights[i], theme);
+            }
+        }
+    }
+}
+
+/// Places the elements around a circle by index, pulled towards the centre by
+/// their distance from their sorted position. A sorted array forms the circle.
+struct Disparity {
+    positions: Vec<usize>,
+    height: u16,
+}
+
+impl Disparity {
+    fn new(nums: &[i32], order: Order, height: u16) -> Disparity {
+        let mut sorted = nums.to_vec();
+        sorted.sort_by(|a, b| order.compare(*a, *b));
+        let mut positions = vec![0; nums.len()];
+        for (i, num) in sorted.iter().enumerate() {
+            positions[*num as usize - 1] = i;
+        }
+        return Disparity { positions, height };
+    }
+
+    /// The cell of the element at `i`.
+    fn position(&self, nums: &[i32], i: usize) -> (usize, usize) {
+        let (width, height) = self.size();
+        let (cx, cy) = ((width - 1) as f64 / 2.0, (height - 1) as f64 / 2.0);
+        let n = nums.len();
+        let distance = self.positions[nums[i] as usize - 1].abs_diff(i);
+        let radius = 1.0 - distance as f64 / n as f64;
+        let angle = TAU * i as f64 / n as f64;
+        let x = (cx + radius * cx * angle.sin()).round() as usize;
+        let y = (cy - radius * cy * angle.cos()).round() as usize;
+        return (x, y);
+    }
+}
+
+impl Renderer for Disparity {
+    fn name(&self) -> &'static str {
+        return "disparity";
+    }
+
+    // Cells are about twice as high as wide.
+    fn size(&self) -> (u16, u16) {
+        return (self.height * 2, self.height);
+    }
+
+    fn index_at(&self, nums: &[i32], x: usize, y: usize) -> Option<usize> {
+        return (0..nums.len()).find(|&i| self.position(nums, i) == (x, y));
+    }
+
+    fn render(
+        &self,
+        nums: &[i32],
+        highlights: &[Option<Highlight>],
+        theme: &Theme,
+        area: Rect,
+        buf: &mut Buffer,
+    ) {
+        // Highlighted elements are drawn last so they stay visible.
+        let mut indices: Vec<usize> = (0..nums.len()).collect();
+        indices.sort_by_key(|&i| highlights[i].is_some());
+        for i in indices {
+            let position = self.position(nums, i);
+            set_mark(buf, area, position, DOT, highlights[i], theme);
+        }
+    }
+}
+
+/// One row per element with bars growing to the right in eighth blocks, for
+/// terminals that are higher than wide.
+struct HorizontalBars {
+    width: u16,
+    height: u16,
+}
+
+impl Renderer for HorizontalBars {
+    fn name(&self) -> &'static str {
+        return "horizontal bars";
+    }
+
+    fn size(&self) -> (u16, u16) {
+        return (self.width, self.height);
+    }
+
+    fn index_at(&self, nums: &[i32], _x: usize, y: usize) -> Option<usize> {
+        return (y < nums.len()).then_some(y);
+    }
+
+    fn render(
+        &self,
+        nums: &[i32],
+        highlights: &[Option<Highlight>],
+        theme: &Theme,
+        area: Rect,
+        buf: &mut Buffer,
+    ) {
+        let width = self.width as usize;
+        for (y, &num) in nums.iter().enumerate() {
+            let eighths = num as usize * width * 8 / nums.len();
+            for x in 0..eighths.div_ceil(8) {
+                let c = BLOCK_EIGHTHS.get(eighths - 8 * x).copied();
+                let c = c.unwrap_or(BLOCK_FULL);
+                set_mark(buf, area, (x, y), c, highlights[y], theme);
+            }
+        }
+    }
+}
+
+/// Maps `t` in `0..1` to a colour along the hue circle, stopping short of red
+/// again so the ends stay apart.
+fn hue_color(t: f64) -> Color {
+    let h = t * 5.0;
+    let x = 1.0 - (h % 2.0 - 1.0).abs();
+    let (r, g, b) = match h as u32 {
+        0 => (1.0, x, 0.0),
+        1 => (x, 1.0, 0.0),
+        2 => (0.0, 1.0, x),
+        3 => (0.0, x, 1.0),
+        _ => (x, 0.0, 1.0),
+    };
+    let channel = |c: f64| (c * 255.0).round() as u8;
+    return Color::Rgb(channel(r), channel(g), channel(b));
+}
+
+/// Turns rows of styled characters into lines, merging neighbours of the same
+/// style into one span.
+pub fn grid_text<'a>(grid: &[Vec<(char, Style)>]) -> Text<'a> {
+    let lines: Vec<Line> = grid
+        .iter()
+        .map(|row| {
+            let mut spans: Vec<Span> = Vec::new();
+            for &(c, style) in row.iter() {
+                match spans.last_mut() {
+                    Some(span) if span.style == style => span.content.to_mut().push(c),
+                    _ => spans.push(Span::styled(c.to_string(), style)),
+                }
+            }
+            Line::from(spans)
+        })
+        .collect();
+    return Text::from(lines);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn draw(renderer: &dyn Renderer, nums: &[i32], highlights: &[Option<Highlight>]) -> Buffer {
+        let (width, height) = renderer.size();
+        let area = Rect::new(0, 0, width, height);
+        let mut buf = Buffer::empty(area);
+        Chart::new(renderer, nums, highlights, &Theme::default()).render(area, &mut buf);
+        return buf;
+    }
+
+    fn rows(buf: &Buffer) -> Vec<String> {
+        let area = buf.area;
+        return (area.top()..area.bottom())
+            .map(|y| {
+                (area.left()..area.right())
+                    .map(|x| buf.get(x, y).symbol())
+                    .collect()
+            })
+            .collect();
+    }
+
+    #[test]
+    fn test_columns() {
+        let values = [1, 2, 3, 4, 5, 6, 9, 10].map(Some);
+        let mut highlights = [None; 8];
+        highlights[6] = Some(Highlight::Compare);
+        let area = Rect::new(0, 0, 8, 3);
+        let mut buf = Buffer::empty(area);
+        let theme = Theme::default();
+        Columns::new(&values, &highlights, &theme).render(area, &mut buf);
+        assert_eq!(vec!["      ▂▄", "    ▂▄██", "▂▄▆█████"], rows(&buf));
+        assert_eq!(Color::LightCyan, buf.get(6, 0).fg);
+        assert_eq!(Color::Reset, buf.get(7, 0).fg);
+
+        let mut buf = Buffer::empty(Rect::new(0, 0, 5, 1));
+        Columns::new(&[Some(4), None, Some(1)], &[None; 3], &theme).render(buf.area, &mut buf);
+        assert_eq!(vec![" █ ▂ "], rows(&buf));
+    }
+
+    #[test]
+    fn test_chart_block() {
+        let bars = Bars {
+            width: 2,
+            height: 1,
+        };
+        let area = Rect::new(0, 0, 6, 3);
+        let mut buf = Buffer::empty(area);
+        let block = Block::default().borders(ratatui::widgets::Borders::ALL);
+        Chart::new(&bars, &[2, 1], &[None; 2], &Theme::default())
+            .block(block)
+            .render(area, &mut buf);
+        assert_eq!(vec!["┌────┐", "│ ▄▂ │", "└────┘"], rows(&buf));
+    }
+
+    #[test]
+    fn test_renderers_clipped() {
+        let nums: Vec<i32> = (1..=32).rev().collect();
+        let highlights = vec![None; nums.len()];
+        for renderer in renderers(&nums, Order::Ascending, 100) {
+            let buf = draw(renderer.as_ref(), &nums, &highlights);
+            assert!(
+                rows(&buf).iter().any(|row| row.trim() != ""),
+                "{}",
+                renderer.name()
+            );
+
+            // Areas smaller than the chart cut it off.
+            let area = Rect::new(0, 0, 3, 2);
+            let mut buf = Buffer::empty(area);
+            renderer.render(&nums, &highlights, &Theme::default(), area, &mut buf);
+        }
+    }
+
+    #[test]
+    fn test_braille() {
+        let braille = Braille {
+            width: 2,
+            height: 1,
+        };
+        let highlights = [None, Some(Highlight::Compare), None, None];
+        let buf = draw(&braille, &[4, 1, 2, 3], &highlights);
+        assert_eq!(vec!["⣇⣴"], rows(&buf));
+        assert_eq!(Color::LightCyan, buf.get(0, 0).fg);
+        assert_eq!(Color::Reset, buf.get(1, 0).fg);
+
+        let (width, height) = renderers(&[1; 300], Order::Ascending, 20)[1].size();
+        assert_eq!((150, 20), (width, height));
+    }
+
+    #[test]
+    fn test_dots() {
+        let dots = Dots {
+            width: 4,
+            height: 2,
+        };
+        let buf = draw(
+            &dots,
+            &[1, 4, 2, 3],
+            &[None, None, Some(Highlight::Compare), None],
+        );
+        assert_eq!(vec![" ▀ ▄", "▄ ▀ "], rows(&buf));
+        assert_eq!(Color::LightCyan, buf.get(2, 1).fg);
+    }
+
+    #[test]
+    fn test_hue_strip() {
+        let strip = HueStrip {
+            width: 3,
+            height: 2,
+        };
+        let buf = draw(&strip, &[3, 1, 2], &[None, Some(Highlight::Swap), None]);
+        assert_eq!(vec!["███", " ▲ "], rows(&buf));
+        assert_eq!(Color::Rgb(255, 0, 0), buf.get(1, 0).fg);
+        assert_eq!(Color::LightGreen, buf.get(1, 1).fg);
+        assert_eq!(Color::Rgb(255, 0, 255), buf.get(0, 0).fg);
+
+        // Two elements still get a row of colour.
+        let charts = renderers(&[2, 1], Order::Ascending, 10);
+        let hue = charts.iter().find(|chart| chart.name() == "hue").unwrap();
+        assert_eq!((2, 2), hue.size());
+    }
+
+    #[test]
+    fn test_disparity() {
+        let sorted = Disparity::new(&[1, 2, 3, 4], Order::Ascending, 3);
+        let buf = draw(&sorted, &[1, 2, 3, 4], &[None; 4]);
+        assert_eq!(vec!["   •  ", "•    •", "   •  "], rows(&buf));
+        // The reversed array is sorted in descending order.
+        let reversed = Disparity::new(&[4, 3, 2, 1], Order::Descending, 3);
+        assert_eq!(
+            rows(&buf),
+            rows(&draw(&reversed, &[4, 3, 2, 1], &[None; 4]))
+        );
+    }
+
+    #[test]
+    fn test_horizontal_bars() {
+        let bars = HorizontalBars {
+            width: 2,
+            height: 3,
+        };
+        let buf = draw(&bars, &[3, 1, 2], &[None; 3]);
+        assert_eq!(vec!["██", "▋ ", "█▎"], rows(&buf));
+    }
+
+    #[test]
+    fn test_index_at() {
+        let nums = [3, 1, 4, 2];
+        let charts = renderers(&nums, Order::Ascending, 10);
+        let chart = |name| charts.iter().find(|chart| chart.name() == name).unwrap();
+        assert_eq!(Some(2), chart("bars").index_at(&nums, 2, 0));
+        assert_eq!(None, chart("bars").index_at(&nums, 4, 0));
+        assert_eq!(Some(2), chart("braille").index_at(&nums, 1, 0));
+        assert_eq!(Some(3), chart("horizontal bars").index_at(&nums, 0, 3));
+
+        // The first element sits at the top of the circle when in place.
+        let disparity = Disparity::new(&[1, 2, 3, 4], Order::Ascending, 3);
+        assert_eq!(Some(0), disparity.index_at(&[1, 2, 3, 4], 3, 0));
+        assert_eq!(None, disparity.index_at(&[1, 2, 3, 4], 3, 1));
+    }
+
+    #[test]
+    fn test_mono_glyphs() {
+        let bars = Bars {
+            width: 3,
+            height: 1,
+        };
+        let area = Rect::new(0, 0, 3, 1);
+        let mut buf = Buffer::empty(area);
+        let highlights = [Some(Highlight::Swap), None, Some(Highlight::Compare)];
+        let mono = Theme::builtin("mono").unwrap();
+        Chart::new(&bars, &[4, 4, 4], &highlights, &mono).render(area, &mut buf);
+        assert_eq!(vec!["▓█▒"], rows(&buf));
+        assert_eq!(Color::Reset, buf.get(0, 0).fg);
+    }
+}
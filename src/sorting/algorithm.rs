@@ -0,0 +1,140 @@
+use std::{
+    fmt::{Debug, Display},
+    str::FromStr,
+};
+
+use anyhow::anyhow;
+
+use super::{AlgorithmContext, Order};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Family {
+    Exchange,
+    Selection,
+    Insertion,
+    Merge,
+    Partition,
+    Hybrid,
+    Network,
+    /// Algorithms loaded at startup rather than built in.
+    Plugin,
+}
+
+impl Family {
+    pub const ALL: [Family; 8] = [
+        Self::Exchange,
+        Self::Selection,
+        Self::Insertion,
+        Self::Merge,
+        Self::Partition,
+        Self::Hybrid,
+        Self::Network,
+        Self::Plugin,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        return match self {
+            Self::Exchange => "exchange",
+            Self::Selection => "selection",
+            Self::Insertion => "insertion",
+            Self::Merge => "merge",
+            Self::Partition => "partition",
+            Self::Hybrid => "hybrid",
+            Self::Network => "network",
+            Self::Plugin => "plugin",
+        };
+    }
+}
+
+impl Display for Family {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.pad(self.name())
+    }
+}
+
+impl FromStr for Family {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        return Self::ALL
+            .into_iter()
+            .find(|family| family.name() == s)
+            .ok_or_else(|| anyhow!("unknown family '{}'", s));
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OperationKind {
+    Compare,
+    Swap,
+    Insert,
+    Write,
+    Run,
+    Partition,
+    Store,
+    Load,
+    Layer,
+    Heap,
+    Enter,
+    Exit,
+}
+
+impl Display for OperationKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Compare => write!(f, "compare"),
+            Self::Swap => write!(f, "swap"),
+            Self::Insert => write!(f, "insert"),
+            Self::Write => write!(f, "write"),
+            Self::Run => write!(f, "run"),
+            Self::Partition => write!(f, "partition"),
+            Self::Store => write!(f, "store"),
+            Self::Load => write!(f, "load"),
+            Self::Layer => write!(f, "layer"),
+            Self::Heap => write!(f, "heap"),
+            Self::Enter => write!(f, "enter"),
+            Self::Exit => write!(f, "exit"),
+        }
+    }
+}
+
+/// Time complexity in big O notation, e.g. `O(n log n)`.
+#[derive(Debug, Clone, Copy)]
+pub struct Complexity {
+    pub best: &'static str,
+    pub average: &'static str,
+    pub worst: &'static str,
+}
+
+/// Sorts the slice in the order, reporting every step to the context.
+pub type Sort = dyn Fn(&mut [i32], Order, &dyn AlgorithmContext) + Sync;
+
+/// Describes a sorting algorithm in the registry.
+pub struct Algorithm {
+    pub name: &'static str,
+    pub family: Family,
+    pub stable: bool,
+    pub in_place: bool,
+    /// Auxiliary memory in big O notation, including the recursion stack.
+    pub memory: &'static str,
+    pub complexity: Complexity,
+    pub description: &'static str,
+    /// Kinds of operation the algorithm reports to its context.
+    pub operations: &'static [OperationKind],
+    pub sort: &'static Sort,
+}
+
+impl Debug for Algorithm {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Algorithm")
+            .field("name", &self.name)
+            .field("family", &self.family)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Display for Algorithm {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
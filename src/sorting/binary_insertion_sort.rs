@@ -0,0 +1,74 @@
+use super::{
+    Algorithm, AlgorithmContext, Complexity, Family,
+    Operation::{Compare, Insert, Noop},
+    OperationKind, Order,
+};
+
+pub const NAME: &str = "binary insertion sort";
+
+pub const ALGORITHM: Algorithm = Algorithm {
+    name: NAME,
+    family: Family::Insertion,
+    stable: true,
+    in_place: true,
+    memory: "O(1)",
+    complexity: Complexity {
+        best: "O(n log n)",
+        average: "O(n^2)",
+        worst: "O(n^2)",
+    },
+    description: "Insertion sort that finds the insertion point with a binary search, so it needs O(n log n) comparisons but still shifts O(n^2) elements.",
+    operations: &[OperationKind::Compare, OperationKind::Insert],
+    sort: &sort,
+};
+
+pub fn sort(nums: &mut [i32], order: Order, ctx: &dyn AlgorithmContext) {
+    sort_range(nums, 0, 0, nums.len(), order, ctx);
+    ctx.next(Noop(), nums.to_vec());
+}
+
+/// Sorts `low..high` given that `low..start` is already sorted, without reporting
+/// the final `Noop`.
+pub(super) fn sort_range(
+    nums: &mut [i32],
+    low: usize,
+    start: usize,
+    high: usize,
+    order: Order,
+    ctx: &dyn AlgorithmContext,
+) {
+    for i in start.max(low + 1)..high {
+        // Searching for the first greater element keeps equal elements in order.
+        let (mut left, mut right) = (low, i);
+        while left < right {
+            let mid = left + (right - left) / 2;
+            ctx.next(Compare(mid, i), nums.to_vec());
+            if order.greater(nums[mid], nums[i]) {
+                right = mid;
+            } else {
+                left = mid + 1;
+            }
+        }
+        if left != i {
+            nums[left..=i].rotate_right(1);
+            ctx.next(Insert(left), nums.to_vec());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sorting::has_nums;
+    use crate::sorting::is_sorted;
+    use crate::sorting::NoopContext;
+
+    use super::*;
+
+    #[test]
+    fn test_sort() {
+        let nums = &mut [3, 5, 2, 8, 6, 9, 0, 1, 4, 7];
+        sort(nums, Order::Ascending, &NoopContext);
+        assert!(is_sorted(nums));
+        assert!(has_nums(nums));
+    }
+}
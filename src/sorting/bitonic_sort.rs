@@ -0,0 +1,92 @@
+use super::{
+    Algorithm, AlgorithmContext, Complexity, Family,
+    Operation::{Layer, Noop},
+    OperationKind, Order,
+};
+
+pub const NAME: &str = "bitonic sort";
+
+pub const ALGORITHM: Algorithm = Algorithm {
+    name: NAME,
+    family: Family::Network,
+    stable: false,
+    in_place: true,
+    memory: "O(1)",
+    complexity: Complexity {
+        best: "O(n log^2 n)",
+        average: "O(n log^2 n)",
+        worst: "O(n log^2 n)",
+    },
+    description: "Sorting network that merges bitonic sequences of doubling size. The comparators of a layer are independent, so the network sorts in O(log^2 n) parallel steps.",
+    operations: &[OperationKind::Layer],
+    sort: &sort,
+};
+
+pub fn sort(nums: &mut [i32], order: Order, ctx: &dyn AlgorithmContext) {
+    let n = nums.len();
+    // Every comparator moves the smaller element to the lower index, so the network
+    // for the next power of two sorts any length when comparators past the end are dropped.
+    let mut k = 2;
+    while k / 2 < n {
+        let mut j = k / 2;
+        while j > 0 {
+            let pairs = (0..n)
+                .filter_map(|i| {
+                    // The first layer of each merge compares mirrored positions of the block.
+                    let partner = if j == k / 2 { i ^ (k - 1) } else { i ^ j };
+                    (i < partner && partner < n).then_some((i, partner))
+                })
+                .collect();
+            compare_exchange(nums, pairs, order, ctx);
+            j /= 2;
+        }
+        k *= 2;
+    }
+    ctx.next(Noop(), nums.to_vec());
+}
+
+/// Applies a layer of disjoint comparators, each ordering `nums[a]` before `nums[b]`.
+pub(super) fn compare_exchange(
+    nums: &mut [i32],
+    pairs: Vec<(usize, usize)>,
+    order: Order,
+    ctx: &dyn AlgorithmContext,
+) {
+    if pairs.is_empty() {
+        return;
+    }
+    for &(a, b) in pairs.iter() {
+        if order.greater(nums[a], nums[b]) {
+            nums.swap(a, b);
+        }
+    }
+    ctx.next(Layer(pairs), nums.to_vec());
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sorting::has_nums;
+    use crate::sorting::is_sorted;
+    use crate::sorting::NoopContext;
+    use crate::{init_vec, shuffle};
+
+    use super::*;
+
+    #[test]
+    fn test_sort() {
+        let nums = &mut [3, 5, 2, 8, 6, 9, 0, 1, 4, 7];
+        sort(nums, Order::Ascending, &NoopContext);
+        assert!(is_sorted(nums));
+        assert!(has_nums(nums));
+    }
+
+    #[test]
+    fn test_sort_lengths() {
+        for n in 1..70 {
+            let mut nums = init_vec(n);
+            shuffle(&mut nums);
+            sort(&mut nums, Order::Ascending, &NoopContext);
+            assert_eq!(init_vec(n), nums, "{}", n);
+        }
+    }
+}
@@ -0,0 +1,60 @@
+use super::{
+    merge_sort, Algorithm, AlgorithmContext, Complexity, Family, Operation::Noop, OperationKind,
+    Order,
+};
+
+pub const NAME: &str = "bottom-up merge sort";
+
+pub const ALGORITHM: Algorithm = Algorithm {
+    name: NAME,
+    family: Family::Merge,
+    stable: true,
+    in_place: false,
+    memory: "O(n)",
+    complexity: Complexity {
+        best: "O(n log n)",
+        average: "O(n log n)",
+        worst: "O(n log n)",
+    },
+    description: "Merges neighbouring runs of width 1, 2, 4 and so on through an auxiliary buffer, without recursion.",
+    operations: &[
+        OperationKind::Compare,
+        OperationKind::Run,
+        OperationKind::Store,
+        OperationKind::Load,
+    ],
+    sort: &sort,
+};
+
+pub fn sort(nums: &mut [i32], order: Order, ctx: &dyn AlgorithmContext) {
+    let n = nums.len();
+    let mut buffer = vec![None; n];
+    let mut width = 1;
+    while width < n {
+        let mut low = 0;
+        while low + width < n {
+            let high = (low + 2 * width).min(n);
+            merge_sort::merge(nums, &mut buffer, low, low + width, high, order, ctx);
+            low = high;
+        }
+        width *= 2;
+    }
+    ctx.next_buffered(Noop(), nums.to_vec(), buffer);
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sorting::has_nums;
+    use crate::sorting::is_sorted;
+    use crate::sorting::NoopContext;
+
+    use super::*;
+
+    #[test]
+    fn test_sort() {
+        let nums = &mut [3, 5, 2, 8, 6, 9, 0, 1, 4, 7];
+        sort(nums, Order::Ascending, &NoopContext);
+        assert!(is_sorted(nums));
+        assert!(has_nums(nums));
+    }
+}
@@ -1,17 +1,35 @@
 use super::{
-    AlgorithmContext,
+    Algorithm, AlgorithmContext, Complexity, Family,
     Operation::{Compare, Noop, Swap},
+    OperationKind, Order,
 };
 
 pub const NAME: &str = "bubble sort";
 
-pub fn sort(nums: &mut [i32], ctx: &dyn AlgorithmContext) {
+pub const ALGORITHM: Algorithm = Algorithm {
+    name: NAME,
+    family: Family::Exchange,
+    stable: true,
+    in_place: true,
+    memory: "O(1)",
+    complexity: Complexity {
+        best: "O(n)",
+        average: "O(n^2)",
+        worst: "O(n^2)",
+    },
+    description:
+        "Repeatedly swaps adjacent elements that are out of order until a pass makes no swaps.",
+    operations: &[OperationKind::Compare, OperationKind::Swap],
+    sort: &sort,
+};
+
+pub fn sort(nums: &mut [i32], order: Order, ctx: &dyn AlgorithmContext) {
     let len = nums.len();
     for i in 0..len {
         let mut swapped = false;
         for j in 0..len - i - 1 {
             ctx.next(Compare(j, j + 1), nums.to_vec());
-            if nums[j] > nums[j + 1] {
+            if order.greater(nums[j], nums[j + 1]) {
                 nums.swap(j, j + 1);
                 ctx.next(Swap(j, j + 1), nums.to_vec());
                 swapped = true;
@@ -35,7 +53,7 @@ mod tests {
     #[test]
     fn test_sort() {
         let nums = &mut [3, 5, 2, 8, 6, 9, 0, 1, 4, 7];
-        sort(nums, &NoopContext);
+        sort(nums, Order::Ascending, &NoopContext);
         assert!(is_sorted(nums));
         assert!(has_nums(nums));
     }
@@ -0,0 +1,74 @@
+use super::{
+    Algorithm, AlgorithmContext, Complexity, Family,
+    Operation::{Compare, Noop, Swap},
+    OperationKind, Order,
+};
+
+pub const NAME: &str = "cocktail shaker sort";
+
+pub const ALGORITHM: Algorithm = Algorithm {
+    name: NAME,
+    family: Family::Exchange,
+    stable: true,
+    in_place: true,
+    memory: "O(1)",
+    complexity: Complexity {
+        best: "O(n)",
+        average: "O(n^2)",
+        worst: "O(n^2)",
+    },
+    description: "Bubble sort that alternates forward and backward passes, so small elements near the end move quickly too.",
+    operations: &[OperationKind::Compare, OperationKind::Swap],
+    sort: &sort,
+};
+
+pub fn sort(nums: &mut [i32], order: Order, ctx: &dyn AlgorithmContext) {
+    // Elements outside `low..high` are in their final position.
+    let (mut low, mut high) = (0, nums.len());
+    while high - low > 1 {
+        let mut last_swap = low;
+        for i in low..high - 1 {
+            if compare_and_swap(nums, i, order, ctx) {
+                last_swap = i + 1;
+            }
+        }
+        high = last_swap;
+
+        let mut first_swap = high;
+        for i in (low..high.saturating_sub(1)).rev() {
+            if compare_and_swap(nums, i, order, ctx) {
+                first_swap = i + 1;
+            }
+        }
+        low = first_swap;
+    }
+    ctx.next(Noop(), nums.to_vec());
+}
+
+/// Swaps `nums[i]` and `nums[i + 1]` if they are out of order.
+fn compare_and_swap(nums: &mut [i32], i: usize, order: Order, ctx: &dyn AlgorithmContext) -> bool {
+    ctx.next(Compare(i, i + 1), nums.to_vec());
+    if order.greater(nums[i], nums[i + 1]) {
+        nums.swap(i, i + 1);
+        ctx.next(Swap(i, i + 1), nums.to_vec());
+        return true;
+    }
+    return false;
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sorting::has_nums;
+    use crate::sorting::is_sorted;
+    use crate::sorting::NoopContext;
+
+    use super::*;
+
+    #[test]
+    fn test_sort() {
+        let nums = &mut [3, 5, 2, 8, 6, 9, 0, 1, 4, 7];
+        sort(nums, Order::Ascending, &NoopContext);
+        assert!(is_sorted(nums));
+        assert!(has_nums(nums));
+    }
+}
@@ -1,14 +1,39 @@
 use super::{
-    AlgorithmContext,
+    Algorithm, AlgorithmContext, Complexity, Family,
     Operation::{Compare, Noop, Swap},
+    OperationKind, Order,
 };
 
 pub const NAME: &str = "comb sort";
 
-pub fn sort(nums: &mut [i32], ctx: &dyn AlgorithmContext) {
+pub const ALGORITHM: Algorithm = Algorithm {
+    name: NAME,
+    family: Family::Exchange,
+    stable: false,
+    in_place: true,
+    memory: "O(1)",
+    complexity: Complexity {
+        best: "O(n log n)",
+        average: "O(n^2 / 2^p)",
+        worst: "O(n^2)",
+    },
+    description: "Bubble sort over elements a gap apart, shrinking the gap by a factor of 1.3.",
+    operations: &[OperationKind::Compare, OperationKind::Swap],
+    sort: &sort,
+};
+
+/// The shrink factor found to work best empirically.
+pub const SHRINK_FACTOR: f64 = 1.3;
+
+pub fn sort(nums: &mut [i32], order: Order, ctx: &dyn AlgorithmContext) {
+    sort_with(nums, SHRINK_FACTOR, order, ctx);
+}
+
+/// Sorts dividing the gap by `shrink_factor` after each pass, which must be greater than 1.
+fn sort_with(nums: &mut [i32], shrink_factor: f64, order: Order, ctx: &dyn AlgorithmContext) {
+    assert!(shrink_factor > 1.0, "shrink factor must be greater than 1");
     let n = nums.len();
     let mut gap = n;
-    let shrink_factor = 1.3;
     let mut swapped = true;
 
     while gap > 1 || swapped {
@@ -19,10 +44,10 @@ pub fn sort(nums: &mut [i32], ctx: &dyn AlgorithmContext) {
 
         swapped = false;
 
-        for i in 0..n - gap {
+        for i in 0..n.saturating_sub(gap) {
             let j = i + gap;
             ctx.next(Compare(i, j), nums.to_vec());
-            if nums[i] > nums[j] {
+            if order.greater(nums[i], nums[j]) {
                 nums.swap(i, j);
                 ctx.next(Swap(i, j), nums.to_vec());
                 swapped = true;
@@ -44,8 +69,17 @@ mod tests {
     #[test]
     fn test_sort() {
         let nums = &mut [3, 5, 2, 8, 6, 9, 0, 1, 4, 7];
-        sort(nums, &NoopContext);
+        sort(nums, Order::Ascending, &NoopContext);
         assert!(is_sorted(nums));
         assert!(has_nums(nums));
     }
+
+    #[test]
+    fn test_sort_shrink_factors() {
+        for shrink_factor in [1.1, SHRINK_FACTOR, 2.0, 10.0] {
+            let mut nums: Vec<i32> = (0..100).map(|i| i * 37 % 100).collect();
+            sort_with(&mut nums, shrink_factor, Order::Ascending, &NoopContext);
+            assert_eq!((0..100).collect::<Vec<i32>>(), nums, "{}", shrink_factor);
+        }
+    }
 }
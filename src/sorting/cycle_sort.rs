@@ -0,0 +1,130 @@
+use std::{cmp::Ordering, mem};
+
+use super::{
+    Algorithm, AlgorithmContext, Complexity, Family,
+    Operation::{Compare, Noop, Write},
+    OperationKind, Order,
+};
+
+pub const NAME: &str = "cycle sort";
+
+pub const ALGORITHM: Algorithm = Algorithm {
+    name: NAME,
+    family: Family::Selection,
+    stable: false,
+    in_place: true,
+    memory: "O(1)",
+    complexity: Complexity {
+        best: "O(n^2)",
+        average: "O(n^2)",
+        worst: "O(n^2)",
+    },
+    description: "Counts the smaller elements to find where each element belongs and rotates every cycle of misplaced elements, writing each element at most once.",
+    operations: &[OperationKind::Compare, OperationKind::Write],
+    sort: &sort,
+};
+
+pub fn sort(nums: &mut [i32], order: Order, ctx: &dyn AlgorithmContext) {
+    // The element being placed is held aside, so every write puts an element into
+    // its final position. Comparisons with it are shown at `start`, the position
+    // its cycle ends in.
+    for start in 0..nums.len() {
+        let mut item = nums[start];
+        let mut pos = position(nums, start, item, order, ctx);
+        if pos == start {
+            continue;
+        }
+        while pos != start {
+            mem::swap(&mut nums[pos], &mut item);
+            ctx.next(Write(pos), nums.to_vec());
+            pos = position(nums, start, item, order, ctx);
+        }
+        nums[start] = item;
+        ctx.next(Write(start), nums.to_vec());
+    }
+    ctx.next(Noop(), nums.to_vec());
+}
+
+/// Returns where `item` belongs among `nums[start..]`, after the elements equal to
+/// it that are already in place.
+fn position(
+    nums: &[i32],
+    start: usize,
+    item: i32,
+    order: Order,
+    ctx: &dyn AlgorithmContext,
+) -> usize {
+    let mut pos = start;
+    for i in start + 1..nums.len() {
+        ctx.next(Compare(i, start), nums.to_vec());
+        if order.less(nums[i], item) {
+            pos += 1;
+        }
+    }
+    if pos == start {
+        return pos;
+    }
+    loop {
+        ctx.next(Compare(pos, start), nums.to_vec());
+        if order.compare(nums[pos], item) != Ordering::Equal {
+            return pos;
+        }
+        pos += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering::Relaxed};
+
+    use crate::sorting::has_nums;
+    use crate::sorting::is_sorted;
+    use crate::sorting::NoopContext;
+    use crate::sorting::Operation;
+
+    use super::*;
+
+    struct WriteCounter(AtomicUsize);
+
+    impl AlgorithmContext for WriteCounter {
+        fn next(&self, operation: Operation, _: Vec<i32>) {
+            if let Write(_) = operation {
+                self.0.fetch_add(1, Relaxed);
+            }
+        }
+    }
+
+    #[test]
+    fn test_sort() {
+        let nums = &mut [3, 5, 2, 8, 6, 9, 0, 1, 4, 7];
+        sort(nums, Order::Ascending, &NoopContext);
+        assert!(is_sorted(nums));
+        assert!(has_nums(nums));
+    }
+
+    #[test]
+    fn test_sort_duplicates() {
+        let mut nums: Vec<i32> = (0..50).map(|i| i * 7 % 5).collect();
+        let mut expected = nums.clone();
+        expected.sort();
+        sort(&mut nums, Order::Ascending, &NoopContext);
+        assert_eq!(expected, nums);
+    }
+
+    #[test]
+    fn test_sort_writes() {
+        // A single cycle of length 4 writes each of its elements once.
+        let nums = &mut [1, 2, 3, 0];
+        let ctx = WriteCounter(AtomicUsize::new(0));
+        sort(nums, Order::Ascending, &ctx);
+        assert_eq!([0, 1, 2, 3], *nums);
+        assert_eq!(4, ctx.0.load(Relaxed));
+
+        // Elements already in place, including equal ones, are not written.
+        let nums = &mut [0, 2, 1, 1, 3];
+        let ctx = WriteCounter(AtomicUsize::new(0));
+        sort(nums, Order::Ascending, &ctx);
+        assert_eq!([0, 1, 1, 2, 3], *nums);
+        assert_eq!(2, ctx.0.load(Relaxed));
+    }
+}
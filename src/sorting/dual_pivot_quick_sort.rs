@@ -0,0 +1,120 @@
+use super::{
+    Algorithm, AlgorithmContext, Complexity, Family,
+    Operation::{Compare, Enter, Exit, Noop, Partition, Swap},
+    OperationKind, Order,
+};
+
+pub const NAME: &str = "dual pivot quick sort";
+
+pub const ALGORITHM: Algorithm = Algorithm {
+    name: NAME,
+    family: Family::Partition,
+    stable: false,
+    in_place: true,
+    memory: "O(log n)",
+    complexity: Complexity {
+        best: "O(n log n)",
+        average: "O(n log n)",
+        worst: "O(n^2)",
+    },
+    description: "Yaroslavskiy's dual pivot partitioning. The first and last elements split the range into three parts sorted recursively.",
+    operations: &[
+        OperationKind::Compare,
+        OperationKind::Swap,
+        OperationKind::Partition,
+        OperationKind::Enter,
+        OperationKind::Exit,
+    ],
+    sort: &sort,
+};
+
+pub fn sort(nums: &mut [i32], order: Order, ctx: &dyn AlgorithmContext) {
+    if nums.len() > 1 {
+        quick_sort(nums, 0, nums.len() - 1, order, ctx);
+    }
+    ctx.next(Noop(), nums.to_vec());
+}
+
+fn quick_sort(nums: &mut [i32], low: usize, high: usize, order: Order, ctx: &dyn AlgorithmContext) {
+    if low >= high {
+        return;
+    }
+    ctx.next(Enter(low, high + 1), nums.to_vec());
+
+    ctx.next(Compare(low, high), nums.to_vec());
+    if order.greater(nums[low], nums[high]) {
+        swap(nums, low, high, ctx);
+    }
+    let (p, q) = (nums[low], nums[high]);
+
+    // nums[low + 1..l] < p, nums[l..k] between p and q, nums[g + 1..high] > q
+    let mut l = low + 1;
+    let mut g = high - 1;
+    let mut k = l;
+    while k <= g {
+        ctx.next(Compare(k, low), nums.to_vec());
+        if order.less(nums[k], p) {
+            swap(nums, k, l, ctx);
+            l += 1;
+        } else {
+            ctx.next(Compare(k, high), nums.to_vec());
+            if !order.less(nums[k], q) {
+                while k < g {
+                    ctx.next(Compare(g, high), nums.to_vec());
+                    if !order.greater(nums[g], q) {
+                        break;
+                    }
+                    g -= 1;
+                }
+                swap(nums, k, g, ctx);
+                g -= 1;
+                ctx.next(Compare(k, low), nums.to_vec());
+                if order.less(nums[k], p) {
+                    swap(nums, k, l, ctx);
+                    l += 1;
+                }
+            }
+        }
+        k += 1;
+    }
+    l -= 1;
+    g += 1;
+
+    swap(nums, low, l, ctx);
+    swap(nums, high, g, ctx);
+    ctx.next(Partition(low, high + 1, l), nums.to_vec());
+    ctx.next(Partition(l + 1, high + 1, g), nums.to_vec());
+
+    if l > low {
+        quick_sort(nums, low, l - 1, order, ctx);
+    }
+    if g > l + 1 {
+        quick_sort(nums, l + 1, g - 1, order, ctx);
+    }
+    quick_sort(nums, g + 1, high, order, ctx);
+    ctx.next(Exit(low, high + 1), nums.to_vec());
+}
+
+fn swap(nums: &mut [i32], a: usize, b: usize, ctx: &dyn AlgorithmContext) {
+    if a != b {
+        nums.swap(a, b);
+        ctx.next(Swap(a, b), nums.to_vec());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sorting::has_nums;
+    use crate::sorting::is_sorted;
+    use crate::sorting::NoopContext;
+
+    use super::*;
+
+    #[test]
+    fn test_sort() {
+        let nums = &mut [3, 5, 2, 8, 6, 9, 0, 1, 4, 7];
+        sort(nums, Order::Ascending, &NoopContext);
+        assert!(is_sorted(nums));
+        assert!(has_nums(nums));
+    }
+}
@@ -0,0 +1,57 @@
+use super::{
+    Algorithm, AlgorithmContext, Complexity, Family,
+    Operation::{Compare, Noop, Swap},
+    OperationKind, Order,
+};
+
+pub const NAME: &str = "gnome sort";
+
+pub const ALGORITHM: Algorithm = Algorithm {
+    name: NAME,
+    family: Family::Exchange,
+    stable: true,
+    in_place: true,
+    memory: "O(1)",
+    complexity: Complexity {
+        best: "O(n)",
+        average: "O(n^2)",
+        worst: "O(n^2)",
+    },
+    description: "Walks forward while neighbours are in order and steps back after swapping a pair that is not.",
+    operations: &[OperationKind::Compare, OperationKind::Swap],
+    sort: &sort,
+};
+
+pub fn sort(nums: &mut [i32], order: Order, ctx: &dyn AlgorithmContext) {
+    let mut i = 1;
+    while i < nums.len() {
+        ctx.next(Compare(i - 1, i), nums.to_vec());
+        if order.greater(nums[i - 1], nums[i]) {
+            nums.swap(i - 1, i);
+            ctx.next(Swap(i - 1, i), nums.to_vec());
+            if i > 1 {
+                i -= 1;
+            }
+        } else {
+            i += 1;
+        }
+    }
+    ctx.next(Noop(), nums.to_vec());
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sorting::has_nums;
+    use crate::sorting::is_sorted;
+    use crate::sorting::NoopContext;
+
+    use super::*;
+
+    #[test]
+    fn test_sort() {
+        let nums = &mut [3, 5, 2, 8, 6, 9, 0, 1, 4, 7];
+        sort(nums, Order::Ascending, &NoopContext);
+        assert!(is_sorted(nums));
+        assert!(has_nums(nums));
+    }
+}
@@ -0,0 +1,229 @@
+//! Runs every algorithm of the registry on the same inputs: all permutations of
+//! small arrays from the empty one up, random arrays with duplicates and negative
+//! numbers, and sorted or reversed ones, checking the result and the claims of its
+//! metadata.
+
+use std::{
+    sync::atomic::{AtomicUsize, Ordering},
+    thread,
+};
+
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use super::{
+    get_algorithms, record, validate::validate, Algorithm, AlgorithmContext, Operation, Order,
+};
+use crate::init_vec;
+
+/// Operations allowed per unit of the declared worst case complexity.
+const WORK_FACTOR: f64 = 8.0;
+
+/// Counts the elements an algorithm compares or moves.
+struct Counter(AtomicUsize);
+
+impl AlgorithmContext for Counter {
+    fn next(&self, operation: Operation, _: Vec<i32>) {
+        self.0.fetch_add(work(&operation), Ordering::Relaxed);
+    }
+}
+
+fn work(operation: &Operation) -> usize {
+    return match operation {
+        Operation::Compare(_, _)
+        | Operation::Swap(_, _)
+        | Operation::Insert(_)
+        | Operation::Write(_)
+        | Operation::Store(_, _)
+        | Operation::Load(_, _) => 1,
+        Operation::Layer(pairs) => pairs.len(),
+        _ => 0,
+    };
+}
+
+/// The declared worst case of the algorithm for `n` elements, without constants.
+fn worst_case(algorithm: &Algorithm, n: usize) -> f64 {
+    let n = n as f64;
+    let log = n.log2().max(1.0);
+    return match algorithm.complexity.worst {
+        "O(n log n)" => n * log,
+        "O(n log^2 n)" => n * log * log,
+        "O(n^2)" => n * n,
+        complexity => panic!("{}: unknown complexity {}", algorithm, complexity),
+    };
+}
+
+/// Sorts a copy of `nums`, checking that the result is the same elements in order
+/// and that the work done is within the declared worst case.
+fn check(algorithm: &Algorithm, nums: &[i32], order: Order) {
+    let counter = Counter(AtomicUsize::new(0));
+    let mut sorted = nums.to_vec();
+    (algorithm.sort)(&mut sorted, order, &counter);
+    assert!(
+        order.is_sorted(&sorted),
+        "{} {} {:?}",
+        algorithm,
+        order,
+        nums
+    );
+    assert!(same_elements(nums, &sorted), "{} {:?}", algorithm, nums);
+    let work = counter.0.into_inner() as f64;
+    let bound = WORK_FACTOR * worst_case(algorithm, nums.len());
+    assert!(
+        work <= bound,
+        "{} did {} operations on {:?}",
+        algorithm,
+        work,
+        nums
+    );
+}
+
+fn same_elements(a: &[i32], b: &[i32]) -> bool {
+    let (mut a, mut b) = (a.to_vec(), b.to_vec());
+    a.sort();
+    b.sort();
+    return a == b;
+}
+
+/// Calls `f` with every permutation of `nums`, by Heap's algorithm.
+fn permutations(nums: &mut [i32], f: &mut impl FnMut(&[i32])) {
+    let n = nums.len();
+    let mut counters = vec![0; n];
+    f(nums);
+    let mut i = 1;
+    while i < n {
+        if counters[i] < i {
+            if i % 2 == 0 {
+                nums.swap(0, i);
+            } else {
+                nums.swap(counters[i], i);
+            }
+            f(nums);
+            counters[i] += 1;
+            i = 1;
+        } else {
+            counters[i] = 0;
+            i += 1;
+        }
+    }
+}
+
+/// Runs `f` on every algorithm, each on its own thread.
+fn each_algorithm(f: impl Fn(&'static Algorithm) + Sync) {
+    thread::scope(|scope| {
+        for algorithm in get_algorithms() {
+            let f = &f;
+            scope.spawn(move || f(algorithm));
+        }
+    });
+}
+
+#[test]
+fn test_permutations() {
+    each_algorithm(|algorithm| {
+        for n in 0..=8 {
+            permutations(&mut init_vec(n), &mut |nums| {
+                check(algorithm, nums, Order::Ascending)
+            });
+        }
+    });
+}
+
+#[test]
+fn test_permutations_count() {
+    let mut count = 0;
+    permutations(&mut init_vec(5), &mut |_| count += 1);
+    assert_eq!(120, count);
+    let mut seen = Vec::new();
+    permutations(&mut init_vec(3), &mut |nums| seen.push(nums.to_vec()));
+    seen.sort();
+    seen.dedup();
+    assert_eq!(6, seen.len());
+}
+
+#[test]
+fn test_random() {
+    let orders = [
+        Order::Ascending,
+        Order::Descending,
+        Order::Modulo(3),
+        Order::Custom(|a, b| b.abs().cmp(&a.abs())),
+    ];
+    each_algorithm(|algorithm| {
+        let mut rng = StdRng::seed_from_u64(49);
+        for _ in 0..100 {
+            let n = rng.gen_range(0..=64);
+            let nums: Vec<i32> = (0..n).map(|_| rng.gen_range(-10..10)).collect();
+            let order = orders[rng.gen_range(0..orders.len())];
+            check(algorithm, &nums, order);
+            if let Err(divergence) = validate(&record(algorithm, &nums, order), order) {
+                panic!("{} {:?}: {}", algorithm, nums, divergence);
+            }
+        }
+    });
+}
+
+#[test]
+fn test_presorted() {
+    each_algorithm(|algorithm| {
+        for n in [0, 1, 2, 3, 100, 257] {
+            let sorted: Vec<i32> = (0..n).collect();
+            let reversed: Vec<i32> = (0..n).rev().collect();
+            let equal = vec![7; n as usize];
+            let organ_pipe: Vec<i32> = (0..n).map(|i| i.min(n - i)).collect();
+            for nums in [&sorted, &reversed, &equal, &organ_pipe] {
+                check(algorithm, nums, Order::Ascending);
+            }
+        }
+    });
+}
+
+#[test]
+fn test_tiny() {
+    let orders = [
+        Order::Ascending,
+        Order::Descending,
+        Order::Modulo(3),
+        Order::Custom(|a, b| b.cmp(&a)),
+    ];
+    each_algorithm(|algorithm| {
+        for nums in [&[][..], &[-7]] {
+            for order in orders {
+                // Nothing to compare or move, and nothing to panic on.
+                let counter = Counter(AtomicUsize::new(0));
+                let mut sorted = nums.to_vec();
+                (algorithm.sort)(&mut sorted, order, &counter);
+                assert_eq!(nums, sorted, "{}", algorithm);
+                assert_eq!(0, counter.0.into_inner(), "{} {:?}", algorithm, nums);
+                let steps = record(algorithm, nums, order);
+                assert_eq!(nums, steps.last().unwrap().nums, "{}", algorithm);
+                assert!(validate(&steps, order).is_ok(), "{}", algorithm);
+            }
+        }
+    });
+}
+
+/// Keys that tie under the order get their index in the thousands, so that the
+/// result tells whether ties kept their order.
+const INDEX: i32 = 1000;
+
+#[test]
+fn test_stability() {
+    let by_key = Order::Custom(|a, b| a.div_euclid(INDEX).cmp(&b.div_euclid(INDEX)));
+    each_algorithm(|algorithm| {
+        if !algorithm.stable {
+            return;
+        }
+        let mut rng = StdRng::seed_from_u64(50);
+        for _ in 0..50 {
+            let n = rng.gen_range(0..=64);
+            let mut nums: Vec<i32> = (0..n).map(|i| rng.gen_range(-4..4) * INDEX + i).collect();
+            (algorithm.sort)(&mut nums, by_key, &Counter(AtomicUsize::new(0)));
+            for pair in nums.windows(2) {
+                let (a, b) = (pair[0], pair[1]);
+                if a.div_euclid(INDEX) == b.div_euclid(INDEX) {
+                    assert!(a < b, "{} is not stable: {:?}", algorithm, nums);
+                }
+            }
+        }
+    });
+}
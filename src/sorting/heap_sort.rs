@@ -1,46 +1,88 @@
 use super::{
-    AlgorithmContext,
-    Operation::{Compare, Noop, Swap},
+    Algorithm, AlgorithmContext, Complexity, Family,
+    Operation::{Compare, Heap, Noop, Swap},
+    OperationKind, Order,
 };
 
 pub const NAME: &str = "heap sort";
 
-pub fn sort(nums: &mut [i32], ctx: &dyn AlgorithmContext) {
-    let n = nums.len();
+pub const ALGORITHM: Algorithm = Algorithm {
+    name: NAME,
+    family: Family::Selection,
+    stable: false,
+    in_place: true,
+    memory: "O(log n)",
+    complexity: Complexity {
+        best: "O(n log n)",
+        average: "O(n log n)",
+        worst: "O(n log n)",
+    },
+    description: "Builds a max heap and repeatedly moves its root to the end of the array.",
+    operations: &[
+        OperationKind::Compare,
+        OperationKind::Swap,
+        OperationKind::Heap,
+    ],
+    sort: &sort,
+};
+
+pub fn sort(nums: &mut [i32], order: Order, ctx: &dyn AlgorithmContext) {
+    sort_range(nums, 0, nums.len(), order, ctx);
+    ctx.next(Noop(), nums.to_vec());
+}
+
+/// Sorts `low..high` without reporting the final `Noop`, for use by hybrid algorithms.
+pub(super) fn sort_range(
+    nums: &mut [i32],
+    low: usize,
+    high: usize,
+    order: Order,
+    ctx: &dyn AlgorithmContext,
+) {
+    let n = high - low;
+    ctx.next(Heap(low, high), nums.to_vec());
     for i in (0..n / 2).rev() {
-        heapify(nums, n, i, ctx);
+        heapify(nums, low, n, i, order, ctx);
     }
     for i in (1..n).rev() {
-        nums.swap(0, i);
-        ctx.next(Swap(0, i), nums.to_vec());
-        heapify(nums, i, 0, ctx);
+        nums.swap(low, low + i);
+        ctx.next(Swap(low, low + i), nums.to_vec());
+        ctx.next(Heap(low, low + i), nums.to_vec());
+        heapify(nums, low, i, 0, order, ctx);
     }
-    ctx.next(Noop(), nums.to_vec());
 }
 
-fn heapify(nums: &mut [i32], n: usize, i: usize, ctx: &dyn AlgorithmContext) {
+/// Sifts down the node `i` of the heap stored in `low..low + n`.
+fn heapify(
+    nums: &mut [i32],
+    low: usize,
+    n: usize,
+    i: usize,
+    order: Order,
+    ctx: &dyn AlgorithmContext,
+) {
     let mut largest = i;
     let left = 2 * i + 1;
     let right = 2 * i + 2;
 
     if left < n {
-        ctx.next(Compare(left, largest), nums.to_vec());
-        if nums[left] > nums[largest] {
+        ctx.next(Compare(low + left, low + largest), nums.to_vec());
+        if order.greater(nums[low + left], nums[low + largest]) {
             largest = left;
         }
     }
 
     if right < n {
-        ctx.next(Compare(right, largest), nums.to_vec());
-        if nums[right] > nums[largest] {
+        ctx.next(Compare(low + right, low + largest), nums.to_vec());
+        if order.greater(nums[low + right], nums[low + largest]) {
             largest = right;
         }
     }
 
     if largest != i {
-        nums.swap(i, largest);
-        ctx.next(Swap(i, largest), nums.to_vec());
-        heapify(nums, n, largest, ctx);
+        nums.swap(low + i, low + largest);
+        ctx.next(Swap(low + i, low + largest), nums.to_vec());
+        heapify(nums, low, n, largest, order, ctx);
     }
 }
 
@@ -48,15 +90,30 @@ fn heapify(nums: &mut [i32], n: usize, i: usize, ctx: &dyn AlgorithmContext) {
 mod tests {
     use crate::sorting::has_nums;
     use crate::sorting::is_sorted;
+    use crate::sorting::record;
     use crate::sorting::NoopContext;
+    use crate::sorting::Operation;
 
     use super::*;
 
     #[test]
     fn test_sort() {
         let nums = &mut [3, 5, 2, 8, 6, 9, 0, 1, 4, 7];
-        sort(nums, &NoopContext);
+        sort(nums, Order::Ascending, &NoopContext);
         assert!(is_sorted(nums));
         assert!(has_nums(nums));
     }
+
+    #[test]
+    fn test_sort_heap_boundary() {
+        let steps = record(&ALGORITHM, &[3, 1, 4, 2], Order::Ascending);
+        let ends: Vec<usize> = steps
+            .iter()
+            .filter_map(|step| match step.operation {
+                Operation::Heap(0, end) => Some(end),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(vec![4, 3, 2, 1], ends);
+    }
 }
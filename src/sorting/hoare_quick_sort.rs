@@ -0,0 +1,130 @@
+use super::{
+    quick_sort::Pivot,
+    Algorithm, AlgorithmContext, Complexity, Family,
+    Operation::{Compare, Enter, Exit, Noop, Partition, Swap},
+    OperationKind, Order,
+};
+
+pub const NAME: &str = "hoare quick sort";
+
+pub const ALGORITHM: Algorithm = Algorithm {
+    name: NAME,
+    family: Family::Partition,
+    stable: false,
+    in_place: true,
+    memory: "O(log n)",
+    complexity: Complexity {
+        best: "O(n log n)",
+        average: "O(n log n)",
+        worst: "O(n^2)",
+    },
+    description: "Hoare partitioning around the median of three. Scans from both ends and swaps pairs on the wrong side, doing fewer swaps than Lomuto.",
+    operations: &[
+        OperationKind::Compare,
+        OperationKind::Swap,
+        OperationKind::Partition,
+        OperationKind::Enter,
+        OperationKind::Exit,
+    ],
+    sort: &sort,
+};
+
+pub fn sort(nums: &mut [i32], order: Order, ctx: &dyn AlgorithmContext) {
+    sort_with(nums, Pivot::MedianOfThree, order, ctx);
+}
+
+pub fn sort_with(nums: &mut [i32], pivot: Pivot, order: Order, ctx: &dyn AlgorithmContext) {
+    if nums.len() > 1 {
+        quick_sort(nums, 0, nums.len() - 1, pivot, order, ctx);
+    }
+    ctx.next(Noop(), nums.to_vec());
+}
+
+fn quick_sort(
+    nums: &mut [i32],
+    low: usize,
+    high: usize,
+    pivot: Pivot,
+    order: Order,
+    ctx: &dyn AlgorithmContext,
+) {
+    if low >= high {
+        return;
+    }
+    ctx.next(Enter(low, high + 1), nums.to_vec());
+    let pivot_index = partition(nums, low, high, pivot, order, ctx);
+    if pivot_index > low {
+        quick_sort(nums, low, pivot_index - 1, pivot, order, ctx);
+    }
+    quick_sort(nums, pivot_index + 1, high, pivot, order, ctx);
+    ctx.next(Exit(low, high + 1), nums.to_vec());
+}
+
+/// Partitions `low..=high` around the pivot moved to `low`, returning its final index.
+fn partition(
+    nums: &mut [i32],
+    low: usize,
+    high: usize,
+    pivot: Pivot,
+    order: Order,
+    ctx: &dyn AlgorithmContext,
+) -> usize {
+    let selected = pivot.select(nums, low, high, order, ctx);
+    if selected != low {
+        nums.swap(selected, low);
+        ctx.next(Swap(selected, low), nums.to_vec());
+    }
+
+    let pivot = nums[low];
+    let mut i = low;
+    let mut j = high + 1;
+    loop {
+        loop {
+            i += 1;
+            ctx.next(Compare(i, low), nums.to_vec());
+            if !order.less(nums[i], pivot) || i == high {
+                break;
+            }
+        }
+        loop {
+            j -= 1;
+            if j == low {
+                break;
+            }
+            ctx.next(Compare(j, low), nums.to_vec());
+            if !order.greater(nums[j], pivot) {
+                break;
+            }
+        }
+        if i >= j {
+            break;
+        }
+        nums.swap(i, j);
+        ctx.next(Swap(i, j), nums.to_vec());
+    }
+
+    if j != low {
+        nums.swap(low, j);
+        ctx.next(Swap(low, j), nums.to_vec());
+    }
+    ctx.next(Partition(low, high + 1, j), nums.to_vec());
+
+    return j;
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sorting::has_nums;
+    use crate::sorting::is_sorted;
+    use crate::sorting::NoopContext;
+
+    use super::*;
+
+    #[test]
+    fn test_sort() {
+        let nums = &mut [3, 5, 2, 8, 6, 9, 0, 1, 4, 7];
+        sort(nums, Order::Ascending, &NoopContext);
+        assert!(is_sorted(nums));
+        assert!(has_nums(nums));
+    }
+}
@@ -0,0 +1,171 @@
+use super::{
+    Algorithm, AlgorithmContext, Complexity, Family,
+    Operation::{Compare, Enter, Exit, Noop, Run, Swap},
+    OperationKind, Order,
+};
+
+pub const NAME: &str = "in-place merge sort";
+
+pub const ALGORITHM: Algorithm = Algorithm {
+    name: NAME,
+    family: Family::Merge,
+    stable: true,
+    in_place: true,
+    memory: "O(log n)",
+    complexity: Complexity {
+        best: "O(n log n)",
+        average: "O(n log^2 n)",
+        worst: "O(n log^2 n)",
+    },
+    description: "Merges without a buffer by splitting the longer half, binary searching the matching cut in the other half and rotating the blocks between the cuts.",
+    operations: &[
+        OperationKind::Compare,
+        OperationKind::Swap,
+        OperationKind::Run,
+        OperationKind::Enter,
+        OperationKind::Exit,
+    ],
+    sort: &sort,
+};
+
+pub fn sort(nums: &mut [i32], order: Order, ctx: &dyn AlgorithmContext) {
+    merge_sort(nums, 0, nums.len(), order, ctx);
+    ctx.next(Noop(), nums.to_vec());
+}
+
+fn merge_sort(nums: &mut [i32], low: usize, high: usize, order: Order, ctx: &dyn AlgorithmContext) {
+    if high - low > 1 {
+        ctx.next(Enter(low, high), nums.to_vec());
+        let mid = low + (high - low) / 2;
+        merge_sort(nums, low, mid, order, ctx);
+        merge_sort(nums, mid, high, order, ctx);
+        merge(nums, low, mid, high, order, ctx);
+        ctx.next(Run(low, high), nums.to_vec());
+        ctx.next(Exit(low, high), nums.to_vec());
+    }
+}
+
+/// Merges the sorted ranges `low..mid` and `mid..high` by rotation.
+fn merge(
+    nums: &mut [i32],
+    low: usize,
+    mid: usize,
+    high: usize,
+    order: Order,
+    ctx: &dyn AlgorithmContext,
+) {
+    if low == mid || mid == high {
+        return;
+    }
+    if high - low == 2 {
+        ctx.next(Compare(low, mid), nums.to_vec());
+        if order.greater(nums[low], nums[mid]) {
+            nums.swap(low, mid);
+            ctx.next(Swap(low, mid), nums.to_vec());
+        }
+        return;
+    }
+
+    // Everything before `cut1` and `mid..cut2` belongs before everything after them.
+    let (cut1, cut2) = if mid - low > high - mid {
+        let cut1 = low + (mid - low) / 2;
+        (cut1, lower_bound(nums, mid, high, cut1, order, ctx))
+    } else {
+        let cut2 = mid + (high - mid) / 2;
+        (upper_bound(nums, low, mid, cut2, order, ctx), cut2)
+    };
+
+    rotate(nums, cut1, mid, cut2, ctx);
+    let new_mid = cut1 + (cut2 - mid);
+    merge(nums, low, cut1, new_mid, order, ctx);
+    merge(nums, new_mid, cut2, high, order, ctx);
+}
+
+/// Returns the first index in `low..high` whose element is not less than `nums[key]`.
+fn lower_bound(
+    nums: &[i32],
+    mut low: usize,
+    mut high: usize,
+    key: usize,
+    order: Order,
+    ctx: &dyn AlgorithmContext,
+) -> usize {
+    while low < high {
+        let mid = low + (high - low) / 2;
+        ctx.next(Compare(mid, key), nums.to_vec());
+        if order.less(nums[mid], nums[key]) {
+            low = mid + 1;
+        } else {
+            high = mid;
+        }
+    }
+    return low;
+}
+
+/// Returns the first index in `low..high` whose element is greater than `nums[key]`.
+fn upper_bound(
+    nums: &[i32],
+    mut low: usize,
+    mut high: usize,
+    key: usize,
+    order: Order,
+    ctx: &dyn AlgorithmContext,
+) -> usize {
+    while low < high {
+        let mid = low + (high - low) / 2;
+        ctx.next(Compare(mid, key), nums.to_vec());
+        if order.greater(nums[mid], nums[key]) {
+            high = mid;
+        } else {
+            low = mid + 1;
+        }
+    }
+    return low;
+}
+
+/// Swaps the blocks `low..mid` and `mid..high` with three reversals.
+fn rotate(nums: &mut [i32], low: usize, mid: usize, high: usize, ctx: &dyn AlgorithmContext) {
+    if low == mid || mid == high {
+        return;
+    }
+    reverse(nums, low, mid, ctx);
+    reverse(nums, mid, high, ctx);
+    reverse(nums, low, high, ctx);
+}
+
+fn reverse(nums: &mut [i32], low: usize, high: usize, ctx: &dyn AlgorithmContext) {
+    let (mut i, mut j) = (low, high);
+    while i + 1 < j {
+        j -= 1;
+        nums.swap(i, j);
+        ctx.next(Swap(i, j), nums.to_vec());
+        i += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sorting::has_nums;
+    use crate::sorting::is_sorted;
+    use crate::sorting::NoopContext;
+
+    use super::*;
+
+    #[test]
+    fn test_sort() {
+        let nums = &mut [3, 5, 2, 8, 6, 9, 0, 1, 4, 7];
+        sort(nums, Order::Ascending, &NoopContext);
+        assert!(is_sorted(nums));
+        assert!(has_nums(nums));
+    }
+
+    #[test]
+    fn test_sort_stable() {
+        let order = Order::Custom(|a, b| (a / 100).cmp(&(b / 100)));
+        let mut nums: Vec<i32> = (0..100).map(|i| (i * 7 % 5) * 100 + i).collect();
+        let mut expected = nums.clone();
+        expected.sort_by_key(|n| n / 100);
+        sort(&mut nums, order, &NoopContext);
+        assert_eq!(expected, nums);
+    }
+}
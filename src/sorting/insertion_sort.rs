@@ -1,16 +1,45 @@
 use super::{
-    AlgorithmContext,
+    Algorithm, AlgorithmContext, Complexity, Family,
     Operation::{Compare, Insert, Noop},
+    OperationKind, Order,
 };
 
 pub const NAME: &str = "insertion sort";
 
-pub fn sort(nums: &mut [i32], ctx: &dyn AlgorithmContext) {
-    for i in 1..nums.len() {
+pub const ALGORITHM: Algorithm = Algorithm {
+    name: NAME,
+    family: Family::Insertion,
+    stable: true,
+    in_place: true,
+    memory: "O(1)",
+    complexity: Complexity {
+        best: "O(n)",
+        average: "O(n^2)",
+        worst: "O(n^2)",
+    },
+    description: "Takes each element in turn and inserts it into the sorted prefix on its left.",
+    operations: &[OperationKind::Compare, OperationKind::Insert],
+    sort: &sort,
+};
+
+pub fn sort(nums: &mut [i32], order: Order, ctx: &dyn AlgorithmContext) {
+    sort_range(nums, 0, nums.len(), order, ctx);
+    ctx.next(Noop(), nums.to_vec());
+}
+
+/// Sorts `low..high` without reporting the final `Noop`, for use by hybrid algorithms.
+pub(super) fn sort_range(
+    nums: &mut [i32],
+    low: usize,
+    high: usize,
+    order: Order,
+    ctx: &dyn AlgorithmContext,
+) {
+    for i in low + 1..high {
         let mut j = i;
-        while j > 0 {
+        while j > low {
             ctx.next(Compare(j - 1, i), nums.to_vec());
-            if nums[j - 1] > nums[i] {
+            if order.greater(nums[j - 1], nums[i]) {
                 j -= 1;
             } else {
                 break;
@@ -26,8 +55,6 @@ pub fn sort(nums: &mut [i32], ctx: &dyn AlgorithmContext) {
             ctx.next(Insert(j), nums.to_vec());
         }
     }
-
-    ctx.next(Noop(), nums.to_vec());
 }
 
 #[cfg(test)]
@@ -41,7 +68,7 @@ mod tests {
     #[test]
     fn test_sort() {
         let nums = &mut [3, 5, 2, 8, 6, 9, 0, 1, 4, 7];
-        sort(nums, &NoopContext);
+        sort(nums, Order::Ascending, &NoopContext);
         assert!(is_sorted(nums));
         assert!(has_nums(nums));
     }
@@ -0,0 +1,170 @@
+use super::{
+    heap_sort, insertion_sort, Algorithm, AlgorithmContext, Complexity, Family,
+    Operation::{Compare, Enter, Exit, Noop, Partition, Swap},
+    OperationKind, Order,
+};
+
+pub const NAME: &str = "intro sort";
+
+pub const ALGORITHM: Algorithm = Algorithm {
+    name: NAME,
+    family: Family::Hybrid,
+    stable: false,
+    in_place: true,
+    memory: "O(log n)",
+    complexity: Complexity {
+        best: "O(n log n)",
+        average: "O(n log n)",
+        worst: "O(n log n)",
+    },
+    description: "Quick sort with a median of three pivot that switches to heap sort when the recursion gets too deep and to insertion sort on small partitions.",
+    operations: &[
+        OperationKind::Compare,
+        OperationKind::Swap,
+        OperationKind::Insert,
+        OperationKind::Partition,
+        OperationKind::Heap,
+        OperationKind::Enter,
+        OperationKind::Exit,
+    ],
+    sort: &sort,
+};
+
+// Partitions up to this size are left to insertion sort. Production implementations
+// use 16, a smaller value keeps the quick sort phase visible on small arrays.
+const SIZE_THRESHOLD: usize = 8;
+
+pub fn sort(nums: &mut [i32], order: Order, ctx: &dyn AlgorithmContext) {
+    let n = nums.len();
+    if n > 1 {
+        let depth_limit = 2 * n.ilog2();
+        intro_sort(nums, 0, n, depth_limit, order, ctx);
+    }
+    ctx.next(Noop(), nums.to_vec());
+}
+
+fn intro_sort(
+    nums: &mut [i32],
+    low: usize,
+    high: usize,
+    depth_limit: u32,
+    order: Order,
+    ctx: &dyn AlgorithmContext,
+) {
+    if high - low < 2 {
+        return;
+    }
+
+    ctx.next(Enter(low, high), nums.to_vec());
+    if high - low <= SIZE_THRESHOLD {
+        insertion_sort::sort_range(nums, low, high, order, ctx);
+    } else if depth_limit == 0 {
+        heap_sort::sort_range(nums, low, high, order, ctx);
+    } else {
+        let pivot_index = partition(nums, low, high, order, ctx);
+        intro_sort(nums, low, pivot_index, depth_limit - 1, order, ctx);
+        intro_sort(nums, pivot_index + 1, high, depth_limit - 1, order, ctx);
+    }
+    ctx.next(Exit(low, high), nums.to_vec());
+}
+
+/// Partitions `low..high` around the median of its first, middle and last elements.
+fn partition(
+    nums: &mut [i32],
+    low: usize,
+    high: usize,
+    order: Order,
+    ctx: &dyn AlgorithmContext,
+) -> usize {
+    let last = high - 1;
+    let mid = low + (high - low) / 2;
+    sort_pair(nums, low, mid, order, ctx);
+    sort_pair(nums, mid, last, order, ctx);
+    sort_pair(nums, low, mid, order, ctx);
+
+    // The median becomes the pivot at the end of the range.
+    nums.swap(mid, last);
+    ctx.next(Swap(mid, last), nums.to_vec());
+
+    let pivot = nums[last];
+    let mut i = low;
+    for j in low..last {
+        ctx.next(Compare(j, last), nums.to_vec());
+        if order.less(nums[j], pivot) {
+            if i != j {
+                nums.swap(i, j);
+                ctx.next(Swap(i, j), nums.to_vec());
+            }
+            i += 1;
+        }
+    }
+
+    if i != last {
+        nums.swap(i, last);
+        ctx.next(Swap(i, last), nums.to_vec());
+    }
+    ctx.next(Partition(low, high, i), nums.to_vec());
+
+    return i;
+}
+
+fn sort_pair(nums: &mut [i32], a: usize, b: usize, order: Order, ctx: &dyn AlgorithmContext) {
+    ctx.next(Compare(a, b), nums.to_vec());
+    if order.greater(nums[a], nums[b]) {
+        nums.swap(a, b);
+        ctx.next(Swap(a, b), nums.to_vec());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use crate::sorting::has_nums;
+    use crate::sorting::is_sorted;
+    use crate::sorting::NoopContext;
+    use crate::sorting::{Operation, Recorder};
+
+    use super::*;
+
+    #[test]
+    fn test_sort() {
+        let nums = &mut [3, 5, 2, 8, 6, 9, 0, 1, 4, 7];
+        sort(nums, Order::Ascending, &NoopContext);
+        assert!(is_sorted(nums));
+        assert!(has_nums(nums));
+    }
+
+    /// Sorts `nums` with the given depth limit, returning whether heap sort ran.
+    fn heap_sorted(nums: &mut [i32], depth_limit: u32) -> bool {
+        let recorder = Recorder(Mutex::new(Vec::new()));
+        intro_sort(
+            nums,
+            0,
+            nums.len(),
+            depth_limit,
+            Order::Ascending,
+            &recorder,
+        );
+        return recorder
+            .0
+            .into_inner()
+            .unwrap()
+            .iter()
+            .any(|step| matches!(step.operation, Operation::Heap(_, _)));
+    }
+
+    #[test]
+    fn test_sort_depth_limit() {
+        // A small depth limit forces the heap sort fallback.
+        let nums = &mut (0..64).rev().collect::<Vec<i32>>();
+        assert!(heap_sorted(nums, 1));
+        assert!(is_sorted(nums));
+        assert!(has_nums(nums));
+
+        // Without one, quick sort sees the same input through.
+        let nums = &mut (0..64).rev().collect::<Vec<i32>>();
+        assert!(!heap_sorted(nums, 64));
+        assert!(is_sorted(nums));
+    }
+}
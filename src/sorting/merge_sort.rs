@@ -1,49 +1,94 @@
 use super::{
-    AlgorithmContext,
-    Operation::{Compare, Insert, Noop},
+    Algorithm, AlgorithmContext, Complexity, Family,
+    Operation::{Compare, Enter, Exit, Load, Noop, Run, Store},
+    OperationKind, Order,
 };
 
 pub const NAME: &str = "merge sort";
 
-pub fn sort(nums: &mut [i32], ctx: &dyn AlgorithmContext) {
-    merge_sort(nums, 0, nums.len() - 1, ctx);
-    ctx.next(Noop(), nums.to_vec());
+pub const ALGORITHM: Algorithm = Algorithm {
+    name: NAME,
+    family: Family::Merge,
+    stable: true,
+    in_place: false,
+    memory: "O(n)",
+    complexity: Complexity {
+        best: "O(n log n)",
+        average: "O(n log n)",
+        worst: "O(n log n)",
+    },
+    description: "Sorts both halves recursively, merges them into an auxiliary buffer and copies the result back.",
+    operations: &[
+        OperationKind::Compare,
+        OperationKind::Run,
+        OperationKind::Store,
+        OperationKind::Load,
+        OperationKind::Enter,
+        OperationKind::Exit,
+    ],
+    sort: &sort,
+};
+
+pub fn sort(nums: &mut [i32], order: Order, ctx: &dyn AlgorithmContext) {
+    let mut buffer = vec![None; nums.len()];
+    merge_sort(nums, &mut buffer, 0, nums.len(), order, ctx);
+    ctx.next_buffered(Noop(), nums.to_vec(), buffer);
 }
 
-fn merge_sort(nums: &mut [i32], low: usize, high: usize, ctx: &dyn AlgorithmContext) {
-    if low < high {
+fn merge_sort(
+    nums: &mut [i32],
+    buffer: &mut [Option<i32>],
+    low: usize,
+    high: usize,
+    order: Order,
+    ctx: &dyn AlgorithmContext,
+) {
+    if high - low > 1 {
+        ctx.next_buffered(Enter(low, high), nums.to_vec(), buffer.to_vec());
         let mid = low + (high - low) / 2;
 
         // Recursively sort each half
-        merge_sort(nums, low, mid, ctx);
-        merge_sort(nums, mid + 1, high, ctx);
+        merge_sort(nums, buffer, low, mid, order, ctx);
+        merge_sort(nums, buffer, mid, high, order, ctx);
 
-        // Merge the sorted halves in place
-        merge(nums, low, mid, high, ctx);
+        merge(nums, buffer, low, mid, high, order, ctx);
+        ctx.next_buffered(Exit(low, high), nums.to_vec(), buffer.to_vec());
     }
 }
 
-fn merge(nums: &mut [i32], low: usize, mut mid: usize, high: usize, ctx: &dyn AlgorithmContext) {
-    let mut i = low;
-    let mut j = mid + 1;
-
-    while i <= mid && j <= high {
-        ctx.next(Compare(i, j), nums.to_vec());
-        if nums[i] <= nums[j] {
-            i += 1;
+/// Merges the sorted ranges `low..mid` and `mid..high` into `buffer[low..high]`
+/// and moves the result back.
+pub(super) fn merge(
+    nums: &mut [i32],
+    buffer: &mut [Option<i32>],
+    low: usize,
+    mid: usize,
+    high: usize,
+    order: Order,
+    ctx: &dyn AlgorithmContext,
+) {
+    let (mut i, mut j) = (low, mid);
+    for k in low..high {
+        let take_left = if i == mid {
+            false
+        } else if j == high {
+            true
         } else {
-            let temp = nums[j];
-            for k in (i..=mid).rev() {
-                nums[k + 1] = nums[k];
-            }
-            nums[i] = temp;
-            ctx.next(Insert(i), nums.to_vec());
+            ctx.next_buffered(Compare(i, j), nums.to_vec(), buffer.to_vec());
+            // Taking from the left on ties keeps the sort stable.
+            !order.greater(nums[i], nums[j])
+        };
+        let from = if take_left { &mut i } else { &mut j };
+        buffer[k] = Some(nums[*from]);
+        ctx.next_buffered(Store(*from, k), nums.to_vec(), buffer.to_vec());
+        *from += 1;
+    }
 
-            i += 1;
-            j += 1;
-            mid += 1;
-        }
+    for k in low..high {
+        nums[k] = buffer[k].take().unwrap();
+        ctx.next_buffered(Load(k, k), nums.to_vec(), buffer.to_vec());
     }
+    ctx.next_buffered(Run(low, high), nums.to_vec(), buffer.to_vec());
 }
 
 #[cfg(test)]
@@ -57,8 +102,18 @@ mod tests {
     #[test]
     fn test_sort() {
         let nums = &mut [3, 5, 2, 8, 6, 9, 0, 1, 4, 7];
-        sort(nums, &NoopContext);
+        sort(nums, Order::Ascending, &NoopContext);
         assert!(is_sorted(nums));
         assert!(has_nums(nums));
     }
+
+    #[test]
+    fn test_sort_stable() {
+        let order = Order::Custom(|a, b| (a / 100).cmp(&(b / 100)));
+        let mut nums: Vec<i32> = (0..100).map(|i| (i * 7 % 5) * 100 + i).collect();
+        let mut expected = nums.clone();
+        expected.sort_by_key(|n| n / 100);
+        sort(&mut nums, order, &NoopContext);
+        assert_eq!(expected, nums);
+    }
 }
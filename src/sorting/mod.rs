@@ -1,27 +1,207 @@
-use std::fmt::Display;
+use std::{cmp::Ordering, fmt::Display, str::FromStr, sync::Mutex};
 
+use anyhow::{anyhow, Result};
+
+mod algorithm;
+pub mod binary_insertion_sort;
+pub mod bitonic_sort;
+pub mod bottom_up_merge_sort;
 pub mod bubble_sort;
+pub mod cocktail_shaker_sort;
 pub mod comb_sort;
+pub mod cycle_sort;
+pub mod dual_pivot_quick_sort;
+pub mod gnome_sort;
+#[cfg(test)]
+mod harness;
 pub mod heap_sort;
+pub mod hoare_quick_sort;
+pub mod in_place_merge_sort;
 pub mod insertion_sort;
+pub mod intro_sort;
 pub mod merge_sort;
+pub mod natural_merge_sort;
+pub mod odd_even_merge_sort;
+pub mod odd_even_sort;
+mod parallel;
+pub mod parallel_merge_sort;
+pub mod parallel_quick_sort;
+pub mod pdq_sort;
 pub mod quick_sort;
 pub mod selection_sort;
 pub mod shell_sort;
+pub mod three_way_quick_sort;
+pub mod tim_sort;
+pub mod validate;
+
+pub use algorithm::{Algorithm, Complexity, Family, OperationKind, Sort};
+
+#[derive(Debug, Clone, Copy, Default)]
+pub enum Order {
+    #[default]
+    Ascending,
+    Descending,
+    /// Orders by `value mod k`, values with the same remainder keep ascending order.
+    Modulo(i32),
+    Custom(fn(i32, i32) -> Ordering),
+}
+
+impl Order {
+    pub fn compare(&self, a: i32, b: i32) -> Ordering {
+        return match *self {
+            Self::Ascending => a.cmp(&b),
+            Self::Descending => b.cmp(&a),
+            Self::Modulo(k) => a
+                .rem_euclid(k)
+                .cmp(&b.rem_euclid(k))
+                .then_with(|| a.cmp(&b)),
+            Self::Custom(f) => f(a, b),
+        };
+    }
+
+    pub fn greater(&self, a: i32, b: i32) -> bool {
+        return self.compare(a, b) == Ordering::Greater;
+    }
+
+    pub fn less(&self, a: i32, b: i32) -> bool {
+        return self.compare(a, b) == Ordering::Less;
+    }
+
+    pub fn is_sorted(&self, nums: &[i32]) -> bool {
+        return nums.windows(2).all(|w| !self.greater(w[0], w[1]));
+    }
+}
+
+impl FromStr for Order {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "asc" | "ascending" => Ok(Self::Ascending),
+            "desc" | "descending" => Ok(Self::Descending),
+            _ => {
+                let k = s
+                    .strip_prefix("mod:")
+                    .ok_or_else(|| anyhow!("unknown order '{}'", s))?;
+                match k.parse::<i32>() {
+                    Ok(k) if k > 0 => Ok(Self::Modulo(k)),
+                    _ => Err(anyhow!("invalid modulus '{}'", k)),
+                }
+            }
+        }
+    }
+}
+
+impl Display for Order {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Ascending => write!(f, "ascending"),
+            Self::Descending => write!(f, "descending"),
+            Self::Modulo(k) => write!(f, "mod {}", k),
+            Self::Custom(_) => write!(f, "custom"),
+        }
+    }
+}
 
-pub trait AlgorithmContext {
+/// Receives the steps of an algorithm. Parallel algorithms report from several
+/// threads, so contexts must be `Sync`.
+pub trait AlgorithmContext: Sync {
     fn next(&self, operation: Operation, nums: Vec<i32>);
+
+    /// Reports a step of an algorithm that works through an auxiliary buffer,
+    /// where `None` marks an unused slot.
+    fn next_buffered(&self, operation: Operation, nums: Vec<i32>, _buffer: Vec<Option<i32>>) {
+        self.next(operation, nums);
+    }
+
+    /// Reports a step with all its details, such as the worker of a parallel
+    /// algorithm that made it. Contexts that do not track them get the step
+    /// through `next` or `next_buffered`.
+    fn next_step(&self, step: Step) {
+        match step.buffer {
+            Some(buffer) => self.next_buffered(step.operation, step.nums, buffer),
+            None => self.next(step.operation, step.nums),
+        }
+    }
+
+    /// Reports that the algorithm gave up before sorting, such as a plugin
+    /// breaking its protocol. The steps reported so far stand.
+    fn fail(&self, _message: String) {}
+
+    /// Whether the steps are no longer wanted, such as when the player showing them
+    /// was closed. Algorithms that may run for long, like plugins, stop early.
+    fn cancelled(&self) -> bool {
+        return false;
+    }
+}
+
+/// A step reported by an algorithm.
+#[derive(Debug, Clone)]
+pub struct Step {
+    pub operation: Operation,
+    pub nums: Vec<i32>,
+    pub buffer: Option<Vec<Option<i32>>>,
+    /// The worker thread of a parallel algorithm that made the step.
+    pub worker: Option<usize>,
 }
 
-#[derive(Debug, Clone, Copy)]
+impl Step {
+    pub fn new(operation: Operation, nums: Vec<i32>) -> Step {
+        return Step {
+            operation,
+            nums,
+            buffer: None,
+            worker: None,
+        };
+    }
+}
+
+#[derive(Debug, Clone)]
 pub enum Operation {
     Noop(),
     Compare(usize, usize),
     Swap(usize, usize),
     Insert(usize),
+    /// Overwrites `nums[i]` with a value of the algorithm's choosing.
+    Write(usize),
+    /// Marks `start..end` as a sorted run.
+    Run(usize, usize),
+    /// Marks `start..end` as partitioned around the pivot at the given index.
+    Partition(usize, usize, usize),
+    /// Copies `nums[from]` into `buffer[to]`.
+    Store(usize, usize),
+    /// Moves `buffer[from]` back into `nums[to]`, freeing the buffer slot.
+    Load(usize, usize),
+    /// A layer of disjoint compare-exchanges applied at once, each pair `(a, b)`
+    /// ordering `nums[a]` before `nums[b]`.
+    Layer(Vec<(usize, usize)>),
+    /// Marks `start..end` as a binary heap rooted at `start`.
+    Heap(usize, usize),
+    /// Marks entering the recursive subproblem on `start..end`.
+    Enter(usize, usize),
+    /// Marks leaving the subproblem on `start..end` entered last.
+    Exit(usize, usize),
 }
 
 impl Operation {
+    pub fn kind(&self) -> Option<OperationKind> {
+        return match self {
+            Self::Noop() => None,
+            Self::Compare(_, _) => Some(OperationKind::Compare),
+            Self::Swap(_, _) => Some(OperationKind::Swap),
+            Self::Insert(_) => Some(OperationKind::Insert),
+            Self::Write(_) => Some(OperationKind::Write),
+            Self::Run(_, _) => Some(OperationKind::Run),
+            Self::Partition(_, _, _) => Some(OperationKind::Partition),
+            Self::Store(_, _) => Some(OperationKind::Store),
+            Self::Load(_, _) => Some(OperationKind::Load),
+            Self::Layer(_) => Some(OperationKind::Layer),
+            Self::Heap(_, _) => Some(OperationKind::Heap),
+            Self::Enter(_, _) => Some(OperationKind::Enter),
+            Self::Exit(_, _) => Some(OperationKind::Exit),
+        };
+    }
+
     pub fn adjusted(&self) -> Self {
         return match *self {
             Self::Compare(a, b) => {
@@ -39,6 +219,15 @@ impl Operation {
                 }
             }
             Self::Insert(i) => return Self::Insert(i),
+            Self::Write(i) => return Self::Write(i),
+            Self::Run(start, end) => return Self::Run(start, end),
+            Self::Partition(start, end, pivot) => return Self::Partition(start, end, pivot),
+            Self::Store(from, to) => return Self::Store(from, to),
+            Self::Load(from, to) => return Self::Load(from, to),
+            Self::Layer(ref pairs) => return Self::Layer(pairs.clone()),
+            Self::Heap(start, end) => return Self::Heap(start, end),
+            Self::Enter(start, end) => return Self::Enter(start, end),
+            Self::Exit(start, end) => return Self::Exit(start, end),
             Self::Noop() => return Self::Noop(),
         };
     }
@@ -57,39 +246,117 @@ impl Display for Operation {
             Self::Insert(i) => {
                 write!(f, "insert: {}", i)
             }
+            Self::Write(i) => {
+                write!(f, "write: {}", i)
+            }
+            Self::Run(start, end) => {
+                write!(f, "run: {}..{}", start, end)
+            }
+            Self::Partition(start, end, pivot) => {
+                write!(f, "partition: {}..{} pivot: {}", start, end, pivot)
+            }
+            Self::Store(from, to) => {
+                write!(f, "store: {} -> {}", from, to)
+            }
+            Self::Load(from, to) => {
+                write!(f, "load: {} -> {}", from, to)
+            }
+            Self::Layer(pairs) => {
+                write!(f, "layer: {} comparators", pairs.len())
+            }
+            Self::Heap(start, end) => {
+                write!(f, "heap: {}..{}", start, end)
+            }
+            Self::Enter(start, end) => {
+                write!(f, "enter: {}..{}", start, end)
+            }
+            Self::Exit(start, end) => {
+                write!(f, "exit: {}..{}", start, end)
+            }
         }
     }
 }
 
-pub fn get_algorithms() -> Vec<&'static str> {
-    return vec![
-        bubble_sort::NAME,
-        selection_sort::NAME,
-        insertion_sort::NAME,
-        merge_sort::NAME,
-        shell_sort::NAME,
-        heap_sort::NAME,
-        quick_sort::NAME,
-        comb_sort::NAME,
-    ];
+const ALGORITHMS: &[&Algorithm] = &[
+    &bubble_sort::ALGORITHM,
+    &cocktail_shaker_sort::ALGORITHM,
+    &odd_even_sort::ALGORITHM,
+    &gnome_sort::ALGORITHM,
+    &selection_sort::ALGORITHM,
+    &cycle_sort::ALGORITHM,
+    &insertion_sort::ALGORITHM,
+    &binary_insertion_sort::ALGORITHM,
+    &merge_sort::ALGORITHM,
+    &bottom_up_merge_sort::ALGORITHM,
+    &natural_merge_sort::ALGORITHM,
+    &in_place_merge_sort::ALGORITHM,
+    &parallel_merge_sort::ALGORITHM,
+    &shell_sort::ALGORITHM,
+    &heap_sort::ALGORITHM,
+    &quick_sort::ALGORITHM,
+    &quick_sort::FIRST,
+    &quick_sort::MIDDLE,
+    &quick_sort::RANDOM,
+    &quick_sort::MEDIAN_OF_THREE,
+    &quick_sort::NINTHER,
+    &hoare_quick_sort::ALGORITHM,
+    &three_way_quick_sort::ALGORITHM,
+    &dual_pivot_quick_sort::ALGORITHM,
+    &parallel_quick_sort::ALGORITHM,
+    &comb_sort::ALGORITHM,
+    &intro_sort::ALGORITHM,
+    &tim_sort::ALGORITHM,
+    &pdq_sort::ALGORITHM,
+    &bitonic_sort::ALGORITHM,
+    &odd_even_merge_sort::ALGORITHM,
+];
+
+pub fn get_algorithms() -> Vec<&'static Algorithm> {
+    return ALGORITHMS.to_vec();
 }
 
-pub fn get_algorithm_func<'a>(s: &str) -> impl FnOnce(&mut [i32], &dyn AlgorithmContext) {
-    match s {
-        bubble_sort::NAME => bubble_sort::sort,
-        selection_sort::NAME => selection_sort::sort,
-        insertion_sort::NAME => insertion_sort::sort,
-        merge_sort::NAME => merge_sort::sort,
-        shell_sort::NAME => shell_sort::sort,
-        heap_sort::NAME => heap_sort::sort,
-        quick_sort::NAME => quick_sort::sort,
-        comb_sort::NAME => comb_sort::sort,
-        _ => panic!("algorithm not found"),
+pub fn get_algorithm(name: &str) -> Result<&'static Algorithm> {
+    return ALGORITHMS
+        .iter()
+        .copied()
+        .find(|algorithm| algorithm.name == name)
+        .ok_or_else(|| anyhow!("algorithm not found: {}", name));
+}
+
+struct Recorder(Mutex<Vec<Step>>);
+
+impl AlgorithmContext for Recorder {
+    fn next(&self, operation: Operation, nums: Vec<i32>) {
+        self.next_step(Step::new(operation, nums));
     }
+
+    fn next_buffered(&self, operation: Operation, nums: Vec<i32>, buffer: Vec<Option<i32>>) {
+        self.next_step(Step {
+            buffer: Some(buffer),
+            ..Step::new(operation, nums)
+        });
+    }
+
+    fn next_step(&self, step: Step) {
+        self.0.lock().unwrap().push(step);
+    }
+}
+
+/// Sorts a copy of `nums` and returns every step reported by the algorithm,
+/// starting with the unsorted input.
+pub fn record(algorithm: &Algorithm, nums: &[i32], order: Order) -> Vec<Step> {
+    let recorder = Recorder(Mutex::new(vec![Step::new(
+        Operation::Noop(),
+        nums.to_vec(),
+    )]));
+    (algorithm.sort)(nums.to_vec().as_mut_slice(), order, &recorder);
+    return recorder.0.into_inner().unwrap();
 }
 
+#[cfg(test)]
 struct NoopContext;
 
+#[cfg(test)]
 impl AlgorithmContext for NoopContext {
     fn next(&self, _: Operation, _: Vec<i32>) {}
 }
@@ -114,13 +381,131 @@ mod test {
     use super::*;
 
     #[test]
-    fn test_get_algorithm_func() {
-        _ = get_algorithm_func(bubble_sort::NAME);
+    fn test_get_algorithm() {
+        let algorithm = get_algorithm(bubble_sort::NAME).unwrap();
+        assert_eq!(bubble_sort::NAME, algorithm.name);
+    }
+
+    #[test]
+    fn test_get_algorithm_not_found() {
+        let err = get_algorithm("algorithm").unwrap_err();
+        assert_eq!("algorithm not found: algorithm", err.to_string());
+    }
+
+    #[test]
+    fn test_record() {
+        let nums = [3, 1, 2];
+        let steps = record(&insertion_sort::ALGORITHM, &nums, Order::Ascending);
+        assert_eq!(vec![3, 1, 2], steps.first().unwrap().nums);
+        assert_eq!(vec![1, 2, 3], steps.last().unwrap().nums);
+        assert!(steps
+            .iter()
+            .any(|step| matches!(step.operation, Operation::Insert(_))));
+    }
+
+    #[test]
+    fn test_record_buffered() {
+        let nums = [3, 1, 2];
+        let steps = record(&merge_sort::ALGORITHM, &nums, Order::Ascending);
+        assert!(steps[0].buffer.is_none());
+        assert!(steps[1..].iter().all(|step| step.buffer.is_some()));
+        assert_eq!(
+            vec![None; 3],
+            *steps.last().unwrap().buffer.as_ref().unwrap()
+        );
+        assert!(steps
+            .iter()
+            .any(|step| matches!(step.operation, Operation::Store(_, _))));
+    }
+
+    #[test]
+    fn test_record_calls() {
+        let nums: Vec<i32> = (0..50).map(|i| i * 37 % 50).collect();
+        for algorithm in get_algorithms() {
+            if !algorithm.operations.contains(&OperationKind::Enter) {
+                continue;
+            }
+            let mut stack: Vec<(usize, usize)> = vec![];
+            for step in record(algorithm, &nums, Order::Ascending) {
+                match step.operation {
+                    Operation::Enter(start, end) => {
+                        let (low, high) = stack.last().copied().unwrap_or((0, nums.len()));
+                        assert!(low <= start && start < end && end <= high, "{}", algorithm);
+                        stack.push((start, end));
+                    }
+                    Operation::Exit(start, end) => {
+                        assert_eq!(Some((start, end)), stack.pop(), "{}", algorithm);
+                    }
+                    _ => {}
+                }
+            }
+            assert!(stack.is_empty(), "{}", algorithm);
+        }
+    }
+
+    #[test]
+    fn test_get_algorithms_unique_names() {
+        let algorithms = get_algorithms();
+        for (i, algorithm) in algorithms.iter().enumerate() {
+            assert!(algorithms[i + 1..].iter().all(|a| a.name != algorithm.name));
+        }
+    }
+
+    struct BoundsContext(usize, &'static Algorithm);
+
+    impl AlgorithmContext for BoundsContext {
+        fn next(&self, operation: Operation, nums: Vec<i32>) {
+            assert_eq!(self.0, nums.len());
+            if let Operation::Compare(a, b) = operation {
+                assert!(a < self.0 && b < self.0 && a != b);
+            }
+            if let Some(kind) = operation.kind() {
+                assert!(self.1.operations.contains(&kind), "{} {}", self.1, kind);
+            }
+        }
+
+        fn next_buffered(&self, operation: Operation, nums: Vec<i32>, buffer: Vec<Option<i32>>) {
+            assert_eq!(self.0, buffer.len());
+            if let Operation::Store(from, to) | Operation::Load(from, to) = operation {
+                assert!(from < self.0 && to < self.0);
+            }
+            self.next(operation, nums);
+        }
+    }
+
+    #[test]
+    fn test_sort_orders() {
+        let orders = [
+            Order::Descending,
+            Order::Modulo(3),
+            Order::Custom(|a, b| (a % 2).cmp(&(b % 2)).then(b.cmp(&a))),
+        ];
+        for algorithm in get_algorithms() {
+            for order in orders {
+                let nums = &mut [3, 5, 2, 8, 6, 9, 0, 1, 4, 7];
+                let ctx = BoundsContext(nums.len(), algorithm);
+                (algorithm.sort)(nums, order, &ctx);
+                assert!(order.is_sorted(nums), "{} {}", algorithm, order);
+                assert!(has_nums(nums));
+            }
+        }
+    }
+
+    #[test]
+    fn test_order_compare() {
+        assert_eq!(Ordering::Less, Order::Ascending.compare(1, 2));
+        assert_eq!(Ordering::Greater, Order::Descending.compare(1, 2));
+        assert_eq!(Ordering::Less, Order::Modulo(3).compare(3, 1));
+        assert_eq!(Ordering::Less, Order::Modulo(3).compare(1, 4));
+        assert_eq!(Ordering::Less, Order::Modulo(3).compare(1, -1));
     }
 
     #[test]
-    #[should_panic(expected = "algorithm not found")]
-    fn test_get_algorithm_func_not_found() {
-        _ = get_algorithm_func("algorithm");
+    fn test_order_from_str() {
+        assert!(matches!("asc".parse(), Ok(Order::Ascending)));
+        assert!(matches!("descending".parse(), Ok(Order::Descending)));
+        assert!(matches!("mod:4".parse(), Ok(Order::Modulo(4))));
+        assert!("mod:0".parse::<Order>().is_err());
+        assert!("random".parse::<Order>().is_err());
     }
 }
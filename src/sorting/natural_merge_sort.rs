@@ -0,0 +1,99 @@
+use super::{
+    merge_sort, Algorithm, AlgorithmContext, Complexity, Family,
+    Operation::{Compare, Noop, Run},
+    OperationKind, Order,
+};
+
+pub const NAME: &str = "natural merge sort";
+
+pub const ALGORITHM: Algorithm = Algorithm {
+    name: NAME,
+    family: Family::Merge,
+    stable: true,
+    in_place: false,
+    memory: "O(n)",
+    complexity: Complexity {
+        best: "O(n)",
+        average: "O(n log n)",
+        worst: "O(n log n)",
+    },
+    description: "Splits the input into its already sorted runs and merges neighbouring runs through an auxiliary buffer until one is left.",
+    operations: &[
+        OperationKind::Compare,
+        OperationKind::Run,
+        OperationKind::Store,
+        OperationKind::Load,
+    ],
+    sort: &sort,
+};
+
+pub fn sort(nums: &mut [i32], order: Order, ctx: &dyn AlgorithmContext) {
+    let n = nums.len();
+    let mut buffer = vec![None; n];
+
+    // Run boundaries, the runs are `bounds[k]..bounds[k + 1]`.
+    let mut bounds = vec![0];
+    for i in 1..n {
+        ctx.next_buffered(Compare(i - 1, i), nums.to_vec(), buffer.clone());
+        if order.greater(nums[i - 1], nums[i]) {
+            ctx.next_buffered(
+                Run(bounds[bounds.len() - 1], i),
+                nums.to_vec(),
+                buffer.clone(),
+            );
+            bounds.push(i);
+        }
+    }
+    if n > 0 {
+        ctx.next_buffered(
+            Run(bounds[bounds.len() - 1], n),
+            nums.to_vec(),
+            buffer.clone(),
+        );
+        bounds.push(n);
+    }
+
+    while bounds.len() > 2 {
+        let mut merged = vec![0];
+        for k in (0..bounds.len() - 1).step_by(2) {
+            if k + 2 < bounds.len() {
+                let (low, mid, high) = (bounds[k], bounds[k + 1], bounds[k + 2]);
+                merge_sort::merge(nums, &mut buffer, low, mid, high, order, ctx);
+                merged.push(high);
+            } else {
+                // An odd run out waits for the next pass.
+                merged.push(bounds[k + 1]);
+            }
+        }
+        bounds = merged;
+    }
+
+    ctx.next_buffered(Noop(), nums.to_vec(), buffer);
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::init_vec;
+    use crate::sorting::has_nums;
+    use crate::sorting::is_sorted;
+    use crate::sorting::NoopContext;
+
+    use super::*;
+
+    #[test]
+    fn test_sort() {
+        let nums = &mut [3, 5, 2, 8, 6, 9, 0, 1, 4, 7];
+        sort(nums, Order::Ascending, &NoopContext);
+        assert!(is_sorted(nums));
+        assert!(has_nums(nums));
+    }
+
+    #[test]
+    fn test_sort_runs() {
+        let mut nums = init_vec(50);
+        nums[..20].reverse();
+        nums[35..].reverse();
+        sort(&mut nums, Order::Ascending, &NoopContext);
+        assert_eq!(init_vec(50), nums);
+    }
+}
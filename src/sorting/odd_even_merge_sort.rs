@@ -0,0 +1,75 @@
+use super::{
+    bitonic_sort, Algorithm, AlgorithmContext, Complexity, Family, Operation::Noop, OperationKind,
+    Order,
+};
+
+pub const NAME: &str = "odd-even merge sort";
+
+pub const ALGORITHM: Algorithm = Algorithm {
+    name: NAME,
+    family: Family::Network,
+    stable: false,
+    in_place: true,
+    memory: "O(1)",
+    complexity: Complexity {
+        best: "O(n log^2 n)",
+        average: "O(n log^2 n)",
+        worst: "O(n log^2 n)",
+    },
+    description: "Batcher's sorting network that merges sorted halves by recursively merging their odd and even subsequences. Needs fewer comparators than bitonic sort with the same O(log^2 n) depth.",
+    operations: &[OperationKind::Layer],
+    sort: &sort,
+};
+
+pub fn sort(nums: &mut [i32], order: Order, ctx: &dyn AlgorithmContext) {
+    let n = nums.len();
+    let mut p = 1;
+    while p < n {
+        let mut k = p;
+        while k > 0 {
+            let mut pairs = Vec::new();
+            let mut j = k % p;
+            while j + k < n {
+                for i in 0..k.min(n - j - k) {
+                    // Only compare elements within the same pair of merged blocks.
+                    if (i + j) / (2 * p) == (i + j + k) / (2 * p) {
+                        pairs.push((i + j, i + j + k));
+                    }
+                }
+                j += 2 * k;
+            }
+            bitonic_sort::compare_exchange(nums, pairs, order, ctx);
+            k /= 2;
+        }
+        p *= 2;
+    }
+    ctx.next(Noop(), nums.to_vec());
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sorting::has_nums;
+    use crate::sorting::is_sorted;
+    use crate::sorting::NoopContext;
+    use crate::{init_vec, shuffle};
+
+    use super::*;
+
+    #[test]
+    fn test_sort() {
+        let nums = &mut [3, 5, 2, 8, 6, 9, 0, 1, 4, 7];
+        sort(nums, Order::Ascending, &NoopContext);
+        assert!(is_sorted(nums));
+        assert!(has_nums(nums));
+    }
+
+    #[test]
+    fn test_sort_lengths() {
+        for n in 1..70 {
+            let mut nums = init_vec(n);
+            shuffle(&mut nums);
+            sort(&mut nums, Order::Ascending, &NoopContext);
+            assert_eq!(init_vec(n), nums, "{}", n);
+        }
+    }
+}
@@ -0,0 +1,61 @@
+use super::{
+    Algorithm, AlgorithmContext, Complexity, Family,
+    Operation::{Compare, Noop, Swap},
+    OperationKind, Order,
+};
+
+pub const NAME: &str = "odd-even sort";
+
+pub const ALGORITHM: Algorithm = Algorithm {
+    name: NAME,
+    family: Family::Exchange,
+    stable: true,
+    in_place: true,
+    memory: "O(1)",
+    complexity: Complexity {
+        best: "O(n)",
+        average: "O(n^2)",
+        worst: "O(n^2)",
+    },
+    description: "Alternates between comparing every odd-indexed pair and every even-indexed pair of neighbours. The pairs of a phase are independent, so each phase could run in parallel.",
+    operations: &[OperationKind::Compare, OperationKind::Swap],
+    sort: &sort,
+};
+
+pub fn sort(nums: &mut [i32], order: Order, ctx: &dyn AlgorithmContext) {
+    let n = nums.len();
+    let mut sorted = false;
+    while !sorted {
+        sorted = true;
+        for start in [1, 0] {
+            let mut i = start;
+            while i + 1 < n {
+                ctx.next(Compare(i, i + 1), nums.to_vec());
+                if order.greater(nums[i], nums[i + 1]) {
+                    nums.swap(i, i + 1);
+                    ctx.next(Swap(i, i + 1), nums.to_vec());
+                    sorted = false;
+                }
+                i += 2;
+            }
+        }
+    }
+    ctx.next(Noop(), nums.to_vec());
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sorting::has_nums;
+    use crate::sorting::is_sorted;
+    use crate::sorting::NoopContext;
+
+    use super::*;
+
+    #[test]
+    fn test_sort() {
+        let nums = &mut [3, 5, 2, 8, 6, 9, 0, 1, 4, 7];
+        sort(nums, Order::Ascending, &NoopContext);
+        assert!(is_sorted(nums));
+        assert!(has_nums(nums));
+    }
+}
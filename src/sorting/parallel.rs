@@ -0,0 +1,205 @@
+use std::thread;
+
+use super::{
+    AlgorithmContext,
+    Operation::{self, Compare, Load, Noop, Store, Swap},
+    Order, Step,
+};
+
+// Levels of the recursion that split the work between two threads, giving
+// `1 << PARALLEL_DEPTH` workers.
+pub(super) const PARALLEL_DEPTH: u32 = 2;
+
+/// Returns the worker that takes the right half of a split at `depth`.
+pub(super) fn right_worker(worker: usize, depth: u32) -> usize {
+    return worker + (1 << (PARALLEL_DEPTH - depth - 1));
+}
+
+/// What a worker did, in order, including what the two workers it forked did
+/// while it waited for them.
+enum Entry {
+    Step(usize, Operation),
+    Fork(Vec<Entry>, Vec<Entry>),
+}
+
+/// A worker of a parallel algorithm, owning the range of the array and of the
+/// buffer it sorts. Workers run on their own threads without sharing anything and
+/// log their steps, which are only reported once all of them are done.
+///
+/// Indices are into the whole array, as in the steps reported.
+pub(super) struct Worker<'a> {
+    id: usize,
+    offset: usize,
+    nums: &'a mut [i32],
+    buffer: &'a mut [Option<i32>],
+    order: Order,
+    log: Vec<Entry>,
+}
+
+impl<'a> Worker<'a> {
+    /// Compares `nums[a]` with `nums[b]`.
+    pub(super) fn greater(&mut self, a: usize, b: usize) -> bool {
+        self.log(Compare(a, b));
+        return self
+            .order
+            .greater(self.nums[a - self.offset], self.nums[b - self.offset]);
+    }
+
+    pub(super) fn swap(&mut self, a: usize, b: usize) {
+        self.nums.swap(a - self.offset, b - self.offset);
+        self.log(Swap(a, b));
+    }
+
+    /// Copies `nums[from]` into `buffer[to]`.
+    pub(super) fn store(&mut self, from: usize, to: usize) {
+        self.buffer[to - self.offset] = Some(self.nums[from - self.offset]);
+        self.log(Store(from, to));
+    }
+
+    /// Moves `buffer[from]` back into `nums[to]`.
+    pub(super) fn load(&mut self, from: usize, to: usize) {
+        self.nums[to - self.offset] = self.buffer[from - self.offset].take().unwrap();
+        self.log(Load(from, to));
+    }
+
+    /// Logs an operation that does not change the array, such as a marker.
+    pub(super) fn mark(&mut self, operation: Operation) {
+        self.log(operation);
+    }
+
+    /// Runs `left` on the range before `mid` on this thread and `right` on the rest
+    /// on another, as the worker that takes the right half of a split at `depth`.
+    pub(super) fn fork(
+        &mut self,
+        mid: usize,
+        depth: u32,
+        left: impl FnOnce(&mut Worker),
+        right: impl FnOnce(&mut Worker) + Send,
+    ) {
+        let split = mid - self.offset;
+        let (left_nums, right_nums) = self.nums.split_at_mut(split);
+        // Unbuffered algorithms have an empty buffer to split.
+        let (left_buffer, right_buffer) = self.buffer.split_at_mut(split.min(self.buffer.len()));
+        let mut left_half = Worker {
+            id: self.id,
+            offset: self.offset,
+            nums: left_nums,
+            buffer: left_buffer,
+            order: self.order,
+            log: Vec::new(),
+        };
+        let mut right_half = Worker {
+            id: right_worker(self.id, depth),
+            offset: mid,
+            nums: right_nums,
+            buffer: right_buffer,
+            order: self.order,
+            log: Vec::new(),
+        };
+        thread::scope(|scope| {
+            scope.spawn(|| right(&mut right_half));
+            left(&mut left_half);
+        });
+        self.log.push(Entry::Fork(left_half.log, right_half.log));
+    }
+
+    fn log(&mut self, operation: Operation) {
+        self.log.push(Entry::Step(self.id, operation));
+    }
+}
+
+/// Sorts `nums` with `sort` run by the first worker, then reports the steps of all
+/// workers, taking turns between the ones that ran at the same time so that each
+/// step shows the progress of all of them.
+pub(super) fn run(
+    nums: &mut [i32],
+    buffered: bool,
+    order: Order,
+    ctx: &dyn AlgorithmContext,
+    sort: impl FnOnce(&mut Worker),
+) {
+    let mut state = nums.to_vec();
+    let mut buffer = vec![None; if buffered { nums.len() } else { 0 }];
+    let mut worker = Worker {
+        id: 0,
+        offset: 0,
+        nums,
+        buffer: &mut buffer,
+        order,
+        log: Vec::new(),
+    };
+    sort(&mut worker);
+    let log = worker.log;
+
+    let mut buffer = buffered.then(|| vec![None; state.len()]);
+    for (id, operation) in flatten(log) {
+        match operation {
+            Swap(a, b) => state.swap(a, b),
+            Store(from, to) => buffer.as_mut().unwrap()[to] = Some(state[from]),
+            Load(from, to) => state[to] = buffer.as_mut().unwrap()[from].take().unwrap(),
+            _ => {}
+        }
+        ctx.next_step(Step {
+            operation,
+            nums: state.clone(),
+            buffer: buffer.clone(),
+            worker: Some(id),
+        });
+    }
+    ctx.next_step(Step {
+        buffer,
+        ..Step::new(Noop(), state)
+    });
+}
+
+/// Orders the steps of a log, alternating between the two workers of each fork.
+fn flatten(log: Vec<Entry>) -> Vec<(usize, Operation)> {
+    let mut steps = Vec::new();
+    for entry in log {
+        match entry {
+            Entry::Step(id, operation) => steps.push((id, operation)),
+            Entry::Fork(left, right) => {
+                let mut left = flatten(left).into_iter();
+                let mut right = flatten(right).into_iter();
+                loop {
+                    match (left.next(), right.next()) {
+                        (None, None) => break,
+                        (a, b) => steps.extend(a.into_iter().chain(b)),
+                    }
+                }
+            }
+        }
+    }
+    return steps;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_flatten() {
+        let log = vec![
+            Entry::Step(0, Compare(0, 1)),
+            Entry::Fork(
+                vec![Entry::Step(0, Swap(0, 1)), Entry::Step(0, Swap(1, 2))],
+                vec![Entry::Step(2, Swap(3, 4))],
+            ),
+            Entry::Step(0, Compare(0, 4)),
+        ];
+        let steps: Vec<String> = flatten(log)
+            .iter()
+            .map(|(id, operation)| format!("{} {}", id, operation))
+            .collect();
+        assert_eq!(
+            vec![
+                "0 compare: 0 1",
+                "0 swap: 0 1",
+                "2 swap: 3 4",
+                "0 swap: 1 2",
+                "0 compare: 0 4"
+            ],
+            steps
+        );
+    }
+}
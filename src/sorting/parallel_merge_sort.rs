@@ -0,0 +1,98 @@
+use super::{
+    parallel::{self, Worker, PARALLEL_DEPTH},
+    Algorithm, AlgorithmContext, Complexity, Family,
+    Operation::Run,
+    OperationKind, Order,
+};
+
+pub const NAME: &str = "parallel merge sort";
+
+pub const ALGORITHM: Algorithm = Algorithm {
+    name: NAME,
+    family: Family::Merge,
+    stable: true,
+    in_place: false,
+    memory: "O(n)",
+    complexity: Complexity {
+        best: "O(n log n)",
+        average: "O(n log n)",
+        worst: "O(n log n)",
+    },
+    description: "Merge sort that sorts the two halves on separate threads for the top levels of the recursion, using four workers.",
+    operations: &[
+        OperationKind::Compare,
+        OperationKind::Run,
+        OperationKind::Store,
+        OperationKind::Load,
+    ],
+    sort: &sort,
+};
+
+pub fn sort(nums: &mut [i32], order: Order, ctx: &dyn AlgorithmContext) {
+    let n = nums.len();
+    parallel::run(nums, true, order, ctx, |worker| merge_sort(worker, 0, n, 0));
+}
+
+fn merge_sort(worker: &mut Worker, low: usize, high: usize, depth: u32) {
+    if high - low < 2 {
+        return;
+    }
+    let mid = low + (high - low) / 2;
+    if depth < PARALLEL_DEPTH {
+        worker.fork(
+            mid,
+            depth,
+            |left| merge_sort(left, low, mid, depth + 1),
+            |right| merge_sort(right, mid, high, depth + 1),
+        );
+    } else {
+        merge_sort(worker, low, mid, depth);
+        merge_sort(worker, mid, high, depth);
+    }
+    merge(worker, low, mid, high);
+}
+
+fn merge(worker: &mut Worker, low: usize, mid: usize, high: usize) {
+    let (mut i, mut j) = (low, mid);
+    for k in low..high {
+        if i < mid && (j == high || !worker.greater(i, j)) {
+            worker.store(i, k);
+            i += 1;
+        } else {
+            worker.store(j, k);
+            j += 1;
+        }
+    }
+    for k in low..high {
+        worker.load(k, k);
+    }
+    worker.mark(Run(low, high));
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sorting::has_nums;
+    use crate::sorting::is_sorted;
+    use crate::sorting::record;
+    use crate::sorting::NoopContext;
+
+    use super::*;
+
+    #[test]
+    fn test_sort() {
+        let nums = &mut [3, 5, 2, 8, 6, 9, 0, 1, 4, 7];
+        sort(nums, Order::Ascending, &NoopContext);
+        assert!(is_sorted(nums));
+        assert!(has_nums(nums));
+    }
+
+    #[test]
+    fn test_sort_workers() {
+        let nums: Vec<i32> = (0..64).rev().collect();
+        let steps = record(&ALGORITHM, &nums, Order::Ascending);
+        for worker in 0..1 << PARALLEL_DEPTH {
+            assert!(steps.iter().any(|step| step.worker == Some(worker)));
+        }
+        assert_eq!((0..64).collect::<Vec<i32>>(), steps.last().unwrap().nums);
+    }
+}
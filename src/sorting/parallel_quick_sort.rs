@@ -0,0 +1,112 @@
+use super::{
+    parallel::{self, Worker, PARALLEL_DEPTH},
+    Algorithm, AlgorithmContext, Complexity, Family,
+    Operation::Partition,
+    OperationKind, Order,
+};
+
+pub const NAME: &str = "parallel quick sort";
+
+pub const ALGORITHM: Algorithm = Algorithm {
+    name: NAME,
+    family: Family::Partition,
+    stable: false,
+    in_place: true,
+    memory: "O(log n)",
+    complexity: Complexity {
+        best: "O(n log n)",
+        average: "O(n log n)",
+        worst: "O(n^2)",
+    },
+    description: "Quick sort that sorts both sides of a partition on separate threads for the top levels of the recursion, using up to four workers.",
+    operations: &[
+        OperationKind::Compare,
+        OperationKind::Swap,
+        OperationKind::Partition,
+    ],
+    sort: &sort,
+};
+
+pub fn sort(nums: &mut [i32], order: Order, ctx: &dyn AlgorithmContext) {
+    let n = nums.len();
+    parallel::run(nums, false, order, ctx, |worker| {
+        quick_sort(worker, 0, n, 0)
+    });
+}
+
+fn quick_sort(worker: &mut Worker, low: usize, high: usize, depth: u32) {
+    if high - low < 2 {
+        return;
+    }
+    let pivot = partition(worker, low, high);
+    if depth < PARALLEL_DEPTH {
+        // The pivot stays with the left worker.
+        worker.fork(
+            pivot + 1,
+            depth,
+            |left| quick_sort(left, low, pivot, depth + 1),
+            |right| quick_sort(right, pivot + 1, high, depth + 1),
+        );
+    } else {
+        quick_sort(worker, low, pivot, depth);
+        quick_sort(worker, pivot + 1, high, depth);
+    }
+}
+
+/// Partitions `low..high` around the median of its first, middle and last elements.
+fn partition(worker: &mut Worker, low: usize, high: usize) -> usize {
+    let last = high - 1;
+    let mid = low + (high - low) / 2;
+    for (a, b) in [(low, mid), (mid, last), (low, mid)] {
+        if a != b && worker.greater(a, b) {
+            worker.swap(a, b);
+        }
+    }
+    if mid != last {
+        worker.swap(mid, last);
+    }
+
+    let mut i = low;
+    for j in low..last {
+        if !worker.greater(j, last) {
+            if i != j {
+                worker.swap(i, j);
+            }
+            i += 1;
+        }
+    }
+    if i != last {
+        worker.swap(i, last);
+    }
+    worker.mark(Partition(low, high, i));
+    return i;
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sorting::has_nums;
+    use crate::sorting::is_sorted;
+    use crate::sorting::record;
+    use crate::sorting::NoopContext;
+    use crate::{init_vec, shuffle};
+
+    use super::*;
+
+    #[test]
+    fn test_sort() {
+        let nums = &mut [3, 5, 2, 8, 6, 9, 0, 1, 4, 7];
+        sort(nums, Order::Ascending, &NoopContext);
+        assert!(is_sorted(nums));
+        assert!(has_nums(nums));
+    }
+
+    #[test]
+    fn test_sort_workers() {
+        let mut nums = init_vec(64);
+        shuffle(&mut nums);
+        let steps = record(&ALGORITHM, &nums, Order::Ascending);
+        assert!(steps.iter().any(|step| step.worker == Some(0)));
+        assert!(steps.iter().all(|step| step.worker.is_none_or(|w| w < 4)));
+        assert_eq!(init_vec(64), steps.last().unwrap().nums);
+    }
+}
@@ -0,0 +1,331 @@
+use super::{
+    heap_sort, insertion_sort, Algorithm, AlgorithmContext, Complexity, Family,
+    Operation::{Compare, Insert, Noop, Partition, Swap},
+    OperationKind, Order,
+};
+
+pub const NAME: &str = "pdq sort";
+
+pub const ALGORITHM: Algorithm = Algorithm {
+    name: NAME,
+    family: Family::Hybrid,
+    stable: false,
+    in_place: true,
+    memory: "O(log n)",
+    complexity: Complexity {
+        best: "O(n)",
+        average: "O(n log n)",
+        worst: "O(n log n)",
+    },
+    description: "Pattern-defeating quick sort. Detects already partitioned ranges, groups elements equal to the pivot, shuffles after unbalanced partitions and falls back to heap sort.",
+    operations: &[
+        OperationKind::Compare,
+        OperationKind::Swap,
+        OperationKind::Insert,
+        OperationKind::Partition,
+        OperationKind::Heap,
+    ],
+    sort: &sort,
+};
+
+// The reference implementation uses 24 and 128, smaller values keep the
+// partitioning phase visible on small arrays.
+const INSERTION_SORT_THRESHOLD: usize = 12;
+const NINTHER_THRESHOLD: usize = 64;
+
+const PARTIAL_INSERTION_SORT_LIMIT: usize = 8;
+
+pub fn sort(nums: &mut [i32], order: Order, ctx: &dyn AlgorithmContext) {
+    let n = nums.len();
+    if n > 1 {
+        pdq_sort(nums, 0, n, n.ilog2(), true, order, ctx);
+    }
+    ctx.next(Noop(), nums.to_vec());
+}
+
+fn pdq_sort(
+    nums: &mut [i32],
+    mut low: usize,
+    high: usize,
+    mut bad_allowed: u32,
+    mut leftmost: bool,
+    order: Order,
+    ctx: &dyn AlgorithmContext,
+) {
+    loop {
+        let size = high - low;
+        if size < INSERTION_SORT_THRESHOLD {
+            insertion_sort::sort_range(nums, low, high, order, ctx);
+            return;
+        }
+
+        // Move the pivot to the start of the range.
+        let half = size / 2;
+        if size > NINTHER_THRESHOLD {
+            sort3(nums, low, low + half, high - 1, order, ctx);
+            sort3(nums, low + 1, low + half - 1, high - 2, order, ctx);
+            sort3(nums, low + 2, low + half + 1, high - 3, order, ctx);
+            sort3(nums, low + half - 1, low + half, low + half + 1, order, ctx);
+            nums.swap(low, low + half);
+            ctx.next(Swap(low, low + half), nums.to_vec());
+        } else {
+            sort3(nums, low + half, low, high - 1, order, ctx);
+        }
+
+        // The pivot equals the element before the range, which is not greater than anything
+        // in it, so the elements equal to the pivot can be skipped.
+        if !leftmost {
+            ctx.next(Compare(low - 1, low), nums.to_vec());
+            if !order.less(nums[low - 1], nums[low]) {
+                low = partition_left(nums, low, high, order, ctx) + 1;
+                continue;
+            }
+        }
+
+        let (pivot, already_partitioned) = partition_right(nums, low, high, order, ctx);
+        ctx.next(Partition(low, high, pivot), nums.to_vec());
+
+        let left_size = pivot - low;
+        let right_size = high - pivot - 1;
+        if left_size < size / 8 || right_size < size / 8 {
+            bad_allowed -= 1;
+            if bad_allowed == 0 {
+                heap_sort::sort_range(nums, low, high, order, ctx);
+                return;
+            }
+            if left_size >= INSERTION_SORT_THRESHOLD {
+                swap(nums, low, low + left_size / 4, ctx);
+                swap(nums, pivot - 1, pivot - left_size / 4, ctx);
+            }
+            if right_size >= INSERTION_SORT_THRESHOLD {
+                swap(nums, pivot + 1, pivot + 1 + right_size / 4, ctx);
+                swap(nums, high - 1, high - right_size / 4, ctx);
+            }
+        } else if already_partitioned
+            && partial_insertion_sort(nums, low, pivot, order, ctx)
+            && partial_insertion_sort(nums, pivot + 1, high, order, ctx)
+        {
+            return;
+        }
+
+        pdq_sort(nums, low, pivot, bad_allowed, leftmost, order, ctx);
+        low = pivot + 1;
+        leftmost = false;
+    }
+}
+
+/// Partitions `low..high` around `nums[low]`, moving elements equal to the pivot to
+/// the right side. Returns the pivot position and whether no swaps were needed.
+fn partition_right(
+    nums: &mut [i32],
+    low: usize,
+    high: usize,
+    order: Order,
+    ctx: &dyn AlgorithmContext,
+) -> (usize, bool) {
+    let pivot = nums[low];
+    let mut first = low + 1;
+    let mut last = high;
+
+    // The median selection guarantees an element not less than the pivot at the end.
+    while less(nums, first, low, pivot, order, ctx) {
+        first += 1;
+    }
+    if first - 1 == low {
+        while first < last {
+            last -= 1;
+            if less(nums, last, low, pivot, order, ctx) {
+                break;
+            }
+        }
+    } else {
+        last -= 1;
+        while !less(nums, last, low, pivot, order, ctx) {
+            last -= 1;
+        }
+    }
+
+    let already_partitioned = first >= last;
+    while first < last {
+        swap(nums, first, last, ctx);
+        first += 1;
+        while less(nums, first, low, pivot, order, ctx) {
+            first += 1;
+        }
+        last -= 1;
+        while !less(nums, last, low, pivot, order, ctx) {
+            last -= 1;
+        }
+    }
+
+    let pivot_index = first - 1;
+    if pivot_index != low {
+        swap(nums, low, pivot_index, ctx);
+    }
+    return (pivot_index, already_partitioned);
+}
+
+/// Partitions `low..high` around `nums[low]`, moving elements equal to the pivot to
+/// the left side. Returns the pivot position.
+fn partition_left(
+    nums: &mut [i32],
+    low: usize,
+    high: usize,
+    order: Order,
+    ctx: &dyn AlgorithmContext,
+) -> usize {
+    let pivot = nums[low];
+    let mut first = low;
+    let mut last = high - 1;
+
+    while last > low && greater(nums, last, low, pivot, order, ctx) {
+        last -= 1;
+    }
+    if last + 1 == high {
+        while first < last {
+            first += 1;
+            if greater(nums, first, low, pivot, order, ctx) {
+                break;
+            }
+        }
+    } else {
+        first += 1;
+        while !greater(nums, first, low, pivot, order, ctx) {
+            first += 1;
+        }
+    }
+
+    while first < last {
+        swap(nums, first, last, ctx);
+        last -= 1;
+        while greater(nums, last, low, pivot, order, ctx) {
+            last -= 1;
+        }
+        first += 1;
+        while !greater(nums, first, low, pivot, order, ctx) {
+            first += 1;
+        }
+    }
+
+    if last != low {
+        swap(nums, low, last, ctx);
+    }
+    ctx.next(Partition(low, high, last), nums.to_vec());
+    return last;
+}
+
+/// Insertion sort that gives up once it has moved too many elements.
+/// Returns whether `low..high` was sorted.
+fn partial_insertion_sort(
+    nums: &mut [i32],
+    low: usize,
+    high: usize,
+    order: Order,
+    ctx: &dyn AlgorithmContext,
+) -> bool {
+    let mut moves = 0;
+    for i in low + 1..high {
+        let mut j = i;
+        while j > low {
+            ctx.next(Compare(j - 1, i), nums.to_vec());
+            if order.greater(nums[j - 1], nums[i]) {
+                j -= 1;
+            } else {
+                break;
+            }
+        }
+        if j != i {
+            nums[j..=i].rotate_right(1);
+            ctx.next(Insert(j), nums.to_vec());
+            moves += i - j;
+        }
+        if moves > PARTIAL_INSERTION_SORT_LIMIT {
+            return false;
+        }
+    }
+    return true;
+}
+
+/// Sorts the elements at `a`, `b` and `c`.
+fn sort3(nums: &mut [i32], a: usize, b: usize, c: usize, order: Order, ctx: &dyn AlgorithmContext) {
+    sort2(nums, a, b, order, ctx);
+    sort2(nums, b, c, order, ctx);
+    sort2(nums, a, b, order, ctx);
+}
+
+fn sort2(nums: &mut [i32], a: usize, b: usize, order: Order, ctx: &dyn AlgorithmContext) {
+    ctx.next(Compare(a, b), nums.to_vec());
+    if order.greater(nums[a], nums[b]) {
+        swap(nums, a, b, ctx);
+    }
+}
+
+fn swap(nums: &mut [i32], a: usize, b: usize, ctx: &dyn AlgorithmContext) {
+    nums.swap(a, b);
+    ctx.next(Swap(a, b), nums.to_vec());
+}
+
+/// Compares `nums[i]` with the pivot stored at `pivot_index`.
+fn less(
+    nums: &[i32],
+    i: usize,
+    pivot_index: usize,
+    pivot: i32,
+    order: Order,
+    ctx: &dyn AlgorithmContext,
+) -> bool {
+    ctx.next(Compare(i, pivot_index), nums.to_vec());
+    return order.less(nums[i], pivot);
+}
+
+fn greater(
+    nums: &[i32],
+    i: usize,
+    pivot_index: usize,
+    pivot: i32,
+    order: Order,
+    ctx: &dyn AlgorithmContext,
+) -> bool {
+    ctx.next(Compare(i, pivot_index), nums.to_vec());
+    return order.greater(nums[i], pivot);
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sorting::has_nums;
+    use crate::sorting::is_sorted;
+    use crate::sorting::NoopContext;
+    use crate::{init_vec, shuffle};
+
+    use super::*;
+
+    #[test]
+    fn test_sort() {
+        let nums = &mut [3, 5, 2, 8, 6, 9, 0, 1, 4, 7];
+        sort(nums, Order::Ascending, &NoopContext);
+        assert!(is_sorted(nums));
+        assert!(has_nums(nums));
+    }
+
+    #[test]
+    fn test_sort_patterns() {
+        let mut shuffled = init_vec(200);
+        shuffle(&mut shuffled);
+        let inputs = [
+            init_vec(200),
+            init_vec(200).into_iter().rev().collect(),
+            (0..200).map(|i| i % 3).collect(),
+            (0..200)
+                .map(|i| if i < 100 { i } else { 200 - i })
+                .collect(),
+            shuffled,
+        ];
+        for input in inputs {
+            let mut nums = input.clone();
+            let mut expected = input;
+            expected.sort();
+            sort(&mut nums, Order::Ascending, &NoopContext);
+            assert_eq!(expected, nums);
+        }
+    }
+}
@@ -1,34 +1,214 @@
+use rand::Rng;
+
 use super::{
-    AlgorithmContext,
-    Operation::{Compare, Noop, Swap},
+    Algorithm, AlgorithmContext, Complexity, Family,
+    Operation::{Compare, Enter, Exit, Noop, Partition, Swap},
+    OperationKind, Order, Sort,
 };
 
 pub const NAME: &str = "quick sort";
 
-pub fn sort(nums: &mut [i32], ctx: &dyn AlgorithmContext) {
-    quick_sort_recursive(nums, 0, nums.len() - 1, ctx);
+pub const ALGORITHM: Algorithm = lomuto(
+    NAME,
+    "Partitions around the last element as pivot and sorts both sides recursively.",
+    &sort,
+);
+
+pub const FIRST: Algorithm = lomuto(
+    "quick sort (first)",
+    "Partitions around the first element as pivot, the worst case on sorted input.",
+    &sort_first,
+);
+
+pub const MIDDLE: Algorithm = lomuto(
+    "quick sort (middle)",
+    "Partitions around the middle element as pivot, which handles sorted input well.",
+    &sort_middle,
+);
+
+pub const RANDOM: Algorithm = lomuto(
+    "quick sort (random)",
+    "Partitions around a random element as pivot, so no input is reliably bad.",
+    &sort_random,
+);
+
+pub const MEDIAN_OF_THREE: Algorithm = lomuto(
+    "quick sort (median of 3)",
+    "Partitions around the median of the first, middle and last elements.",
+    &sort_median_of_three,
+);
+
+pub const NINTHER: Algorithm = lomuto(
+    "quick sort (ninther)",
+    "Partitions around Tukey's ninther, the median of three medians of three, on large ranges.",
+    &sort_ninther,
+);
+
+const fn lomuto(name: &'static str, description: &'static str, sort: &'static Sort) -> Algorithm {
+    return Algorithm {
+        name,
+        family: Family::Partition,
+        stable: false,
+        in_place: true,
+        // Both sides are sorted recursively, one level per element on bad input.
+        memory: "O(n)",
+        complexity: Complexity {
+            best: "O(n log n)",
+            average: "O(n log n)",
+            worst: "O(n^2)",
+        },
+        description,
+        operations: &[
+            OperationKind::Compare,
+            OperationKind::Swap,
+            OperationKind::Partition,
+            OperationKind::Enter,
+            OperationKind::Exit,
+        ],
+        sort,
+    };
+}
+
+// Ranges shorter than this use the median of three instead of the ninther.
+const NINTHER_THRESHOLD: usize = 40;
+
+/// Strategy for choosing the pivot of a partition.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Pivot {
+    First,
+    Last,
+    Middle,
+    Random,
+    MedianOfThree,
+    Ninther,
+}
+
+impl Pivot {
+    /// Returns the index of the pivot in `low..=high`, reporting the comparisons needed.
+    pub fn select(
+        &self,
+        nums: &[i32],
+        low: usize,
+        high: usize,
+        order: Order,
+        ctx: &dyn AlgorithmContext,
+    ) -> usize {
+        let mid = low + (high - low) / 2;
+        return match self {
+            Self::First => low,
+            Self::Last => high,
+            Self::Middle => mid,
+            Self::Random => rand::thread_rng().gen_range(low..=high),
+            Self::MedianOfThree | Self::Ninther if high - low < 2 => mid,
+            Self::MedianOfThree => median_of_three(nums, low, mid, high, order, ctx),
+            Self::Ninther => {
+                if high - low + 1 < NINTHER_THRESHOLD {
+                    return median_of_three(nums, low, mid, high, order, ctx);
+                }
+                let d = (high - low + 1) / 8;
+                let a = median_of_three(nums, low, low + d, low + 2 * d, order, ctx);
+                let b = median_of_three(nums, mid - d, mid, mid + d, order, ctx);
+                let c = median_of_three(nums, high - 2 * d, high - d, high, order, ctx);
+                median_of_three(nums, a, b, c, order, ctx)
+            }
+        };
+    }
+}
+
+/// Returns the index of the median of `nums[a]`, `nums[b]` and `nums[c]`.
+pub(super) fn median_of_three(
+    nums: &[i32],
+    a: usize,
+    b: usize,
+    c: usize,
+    order: Order,
+    ctx: &dyn AlgorithmContext,
+) -> usize {
+    ctx.next(Compare(a, b), nums.to_vec());
+    let ab = order.less(nums[a], nums[b]);
+    ctx.next(Compare(b, c), nums.to_vec());
+    let bc = order.less(nums[b], nums[c]);
+    if ab == bc {
+        return b;
+    }
+    ctx.next(Compare(a, c), nums.to_vec());
+    let ac = order.less(nums[a], nums[c]);
+    return if ab == ac { c } else { a };
+}
+
+pub fn sort(nums: &mut [i32], order: Order, ctx: &dyn AlgorithmContext) {
+    sort_with(nums, Pivot::Last, order, ctx);
+}
+
+fn sort_first(nums: &mut [i32], order: Order, ctx: &dyn AlgorithmContext) {
+    sort_with(nums, Pivot::First, order, ctx);
+}
+
+fn sort_middle(nums: &mut [i32], order: Order, ctx: &dyn AlgorithmContext) {
+    sort_with(nums, Pivot::Middle, order, ctx);
+}
+
+fn sort_random(nums: &mut [i32], order: Order, ctx: &dyn AlgorithmContext) {
+    sort_with(nums, Pivot::Random, order, ctx);
+}
+
+fn sort_median_of_three(nums: &mut [i32], order: Order, ctx: &dyn AlgorithmContext) {
+    sort_with(nums, Pivot::MedianOfThree, order, ctx);
+}
+
+fn sort_ninther(nums: &mut [i32], order: Order, ctx: &dyn AlgorithmContext) {
+    sort_with(nums, Pivot::Ninther, order, ctx);
+}
+
+/// Sorts with Lomuto partitioning around the pivot chosen by the strategy.
+pub fn sort_with(nums: &mut [i32], pivot: Pivot, order: Order, ctx: &dyn AlgorithmContext) {
+    if nums.len() > 1 {
+        quick_sort_recursive(nums, 0, nums.len() - 1, pivot, order, ctx);
+    }
     ctx.next(Noop(), nums.to_vec());
 }
 
-fn quick_sort_recursive(nums: &mut [i32], low: usize, high: usize, ctx: &dyn AlgorithmContext) {
+fn quick_sort_recursive(
+    nums: &mut [i32],
+    low: usize,
+    high: usize,
+    pivot: Pivot,
+    order: Order,
+    ctx: &dyn AlgorithmContext,
+) {
     if low < high {
-        let pivot_index = partition(nums, low, high, ctx);
+        ctx.next(Enter(low, high + 1), nums.to_vec());
+        let pivot_index = partition(nums, low, high, pivot, order, ctx);
 
         if pivot_index > 0 {
-            quick_sort_recursive(nums, low, pivot_index - 1, ctx);
+            quick_sort_recursive(nums, low, pivot_index - 1, pivot, order, ctx);
         }
 
-        quick_sort_recursive(nums, pivot_index + 1, high, ctx);
+        quick_sort_recursive(nums, pivot_index + 1, high, pivot, order, ctx);
+        ctx.next(Exit(low, high + 1), nums.to_vec());
     }
 }
 
-fn partition(nums: &mut [i32], low: usize, high: usize, ctx: &dyn AlgorithmContext) -> usize {
+fn partition(
+    nums: &mut [i32],
+    low: usize,
+    high: usize,
+    pivot: Pivot,
+    order: Order,
+    ctx: &dyn AlgorithmContext,
+) -> usize {
+    let selected = pivot.select(nums, low, high, order, ctx);
+    if selected != high {
+        nums.swap(selected, high);
+        ctx.next(Swap(selected, high), nums.to_vec());
+    }
+
     let pivot = nums[high];
     let mut i = low;
 
     for j in low..high {
         ctx.next(Compare(j, high), nums.to_vec());
-        if nums[j] <= pivot {
+        if !order.greater(nums[j], pivot) {
             if i != j {
                 nums.swap(i, j);
                 ctx.next(Swap(i, j), nums.to_vec());
@@ -41,6 +221,7 @@ fn partition(nums: &mut [i32], low: usize, high: usize, ctx: &dyn AlgorithmConte
         nums.swap(i, high);
         ctx.next(Swap(i, high), nums.to_vec());
     }
+    ctx.next(Partition(low, high + 1, i), nums.to_vec());
 
     return i;
 }
@@ -56,8 +237,63 @@ mod tests {
     #[test]
     fn test_sort() {
         let nums = &mut [3, 5, 2, 8, 6, 9, 0, 1, 4, 7];
-        sort(nums, &NoopContext);
+        sort(nums, Order::Ascending, &NoopContext);
         assert!(is_sorted(nums));
         assert!(has_nums(nums));
     }
+
+    #[test]
+    fn test_sort_pivots() {
+        let pivots = [
+            Pivot::First,
+            Pivot::Last,
+            Pivot::Middle,
+            Pivot::Random,
+            Pivot::MedianOfThree,
+            Pivot::Ninther,
+        ];
+        for pivot in pivots {
+            let mut nums: Vec<i32> = (0..100).map(|i| i * 37 % 100).collect();
+            sort_with(&mut nums, pivot, Order::Ascending, &NoopContext);
+            assert_eq!((0..100).collect::<Vec<i32>>(), nums, "{:?}", pivot);
+        }
+    }
+
+    #[test]
+    fn test_sort_depth() {
+        let nums: Vec<i32> = (0..20).collect();
+        let steps = crate::sorting::record(&FIRST, &nums, Order::Ascending);
+        let mut depth: usize = 0;
+        let mut max_depth = 0;
+        for step in steps {
+            match step.operation {
+                Enter(_, _) => depth += 1,
+                Exit(_, _) => depth -= 1,
+                _ => {}
+            }
+            max_depth = max_depth.max(depth);
+        }
+        assert_eq!(19, max_depth);
+    }
+
+    #[test]
+    fn test_median_of_three() {
+        let nums = [2, 1, 3, 3];
+        assert_eq!(
+            0,
+            median_of_three(&nums, 0, 1, 2, Order::Ascending, &NoopContext)
+        );
+        assert_eq!(
+            0,
+            median_of_three(&nums, 1, 0, 2, Order::Ascending, &NoopContext)
+        );
+        assert_eq!(
+            0,
+            median_of_three(&nums, 1, 2, 0, Order::Descending, &NoopContext)
+        );
+        assert_eq!(
+            3,
+            median_of_three(&nums, 0, 2, 3, Order::Ascending, &NoopContext)
+        );
+    }
 }
@@ -1,17 +1,35 @@
 use super::{
-    AlgorithmContext,
+    Algorithm, AlgorithmContext, Complexity, Family,
     Operation::{Compare, Noop, Swap},
+    OperationKind, Order,
 };
 
 pub const NAME: &str = "selection sort";
 
-pub fn sort(nums: &mut [i32], ctx: &dyn AlgorithmContext) {
+pub const ALGORITHM: Algorithm = Algorithm {
+    name: NAME,
+    family: Family::Selection,
+    stable: false,
+    in_place: true,
+    memory: "O(1)",
+    complexity: Complexity {
+        best: "O(n^2)",
+        average: "O(n^2)",
+        worst: "O(n^2)",
+    },
+    description:
+        "Finds the smallest remaining element and swaps it to the front of the unsorted part.",
+    operations: &[OperationKind::Compare, OperationKind::Swap],
+    sort: &sort,
+};
+
+pub fn sort(nums: &mut [i32], order: Order, ctx: &dyn AlgorithmContext) {
     let len = nums.len();
     for left in 0..len {
         let mut smallest = left;
         for right in (left + 1)..len {
             ctx.next(Compare(smallest, right), nums.to_vec());
-            if nums[right] < nums[smallest] {
+            if order.less(nums[right], nums[smallest]) {
                 smallest = right;
             }
         }
@@ -34,7 +52,7 @@ mod tests {
     #[test]
     fn test_sort() {
         let nums = &mut [3, 5, 2, 8, 6, 9, 0, 1, 4, 7];
-        sort(nums, &NoopContext);
+        sort(nums, Order::Ascending, &NoopContext);
         assert!(is_sorted(nums));
         assert!(has_nums(nums));
     }
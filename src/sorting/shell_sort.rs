@@ -1,11 +1,28 @@
 use super::{
-    AlgorithmContext,
+    Algorithm, AlgorithmContext, Complexity, Family,
     Operation::{Compare, Noop, Swap},
+    OperationKind, Order,
 };
 
 pub const NAME: &str = "shell sort";
 
-pub fn sort(nums: &mut [i32], ctx: &dyn AlgorithmContext) {
+pub const ALGORITHM: Algorithm = Algorithm {
+    name: NAME,
+    family: Family::Insertion,
+    stable: false,
+    in_place: true,
+    memory: "O(1)",
+    complexity: Complexity {
+        best: "O(n log n)",
+        average: "O(n^1.5)",
+        worst: "O(n^2)",
+    },
+    description: "Insertion sort over elements a gap apart, halving the gap until it reaches one.",
+    operations: &[OperationKind::Compare, OperationKind::Swap],
+    sort: &sort,
+};
+
+pub fn sort(nums: &mut [i32], order: Order, ctx: &dyn AlgorithmContext) {
     let n = nums.len();
     let mut gap = n / 2;
     while gap > 0 {
@@ -13,7 +30,7 @@ pub fn sort(nums: &mut [i32], ctx: &dyn AlgorithmContext) {
             let mut j = i;
             while j >= gap {
                 ctx.next(Compare(j - gap, j), nums.to_vec());
-                if nums[j - gap] > nums[j] {
+                if order.greater(nums[j - gap], nums[j]) {
                     nums.swap(j - gap, j);
                     ctx.next(Swap(j - gap, j), nums.to_vec());
                     j -= gap;
@@ -38,7 +55,7 @@ mod tests {
     #[test]
     fn test_sort() {
         let nums = &mut [3, 5, 2, 8, 6, 9, 0, 1, 4, 7];
-        sort(nums, &NoopContext);
+        sort(nums, Order::Ascending, &NoopContext);
         assert!(is_sorted(nums));
         assert!(has_nums(nums));
     }
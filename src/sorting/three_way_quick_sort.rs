@@ -0,0 +1,104 @@
+use super::{
+    quick_sort::Pivot,
+    Algorithm, AlgorithmContext, Complexity, Family,
+    Operation::{Compare, Enter, Exit, Noop, Partition, Swap},
+    OperationKind, Order,
+};
+
+pub const NAME: &str = "3-way quick sort";
+
+pub const ALGORITHM: Algorithm = Algorithm {
+    name: NAME,
+    family: Family::Partition,
+    stable: false,
+    in_place: true,
+    memory: "O(log n)",
+    complexity: Complexity {
+        best: "O(n)",
+        average: "O(n log n)",
+        worst: "O(n^2)",
+    },
+    description: "Dijkstra's three-way partitioning into less than, equal to and greater than the pivot. Elements equal to the pivot are never touched again, so duplicates are cheap.",
+    operations: &[
+        OperationKind::Compare,
+        OperationKind::Swap,
+        OperationKind::Partition,
+        OperationKind::Enter,
+        OperationKind::Exit,
+    ],
+    sort: &sort,
+};
+
+pub fn sort(nums: &mut [i32], order: Order, ctx: &dyn AlgorithmContext) {
+    if nums.len() > 1 {
+        quick_sort(nums, 0, nums.len() - 1, order, ctx);
+    }
+    ctx.next(Noop(), nums.to_vec());
+}
+
+fn quick_sort(nums: &mut [i32], low: usize, high: usize, order: Order, ctx: &dyn AlgorithmContext) {
+    if low >= high {
+        return;
+    }
+    ctx.next(Enter(low, high + 1), nums.to_vec());
+
+    let selected = Pivot::MedianOfThree.select(nums, low, high, order, ctx);
+    if selected != low {
+        nums.swap(selected, low);
+        ctx.next(Swap(selected, low), nums.to_vec());
+    }
+
+    // nums[low..lt] < pivot, nums[lt..i] == pivot, nums[gt + 1..=high] > pivot
+    let pivot = nums[low];
+    let mut lt = low;
+    let mut i = low + 1;
+    let mut gt = high;
+    while i <= gt {
+        ctx.next(Compare(i, lt), nums.to_vec());
+        if order.less(nums[i], pivot) {
+            nums.swap(lt, i);
+            ctx.next(Swap(lt, i), nums.to_vec());
+            lt += 1;
+            i += 1;
+        } else if order.greater(nums[i], pivot) {
+            if i != gt {
+                nums.swap(i, gt);
+                ctx.next(Swap(i, gt), nums.to_vec());
+            }
+            gt -= 1;
+        } else {
+            i += 1;
+        }
+    }
+    ctx.next(Partition(low, high + 1, lt), nums.to_vec());
+
+    if lt > low {
+        quick_sort(nums, low, lt - 1, order, ctx);
+    }
+    quick_sort(nums, gt + 1, high, order, ctx);
+    ctx.next(Exit(low, high + 1), nums.to_vec());
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sorting::has_nums;
+    use crate::sorting::is_sorted;
+    use crate::sorting::NoopContext;
+
+    use super::*;
+
+    #[test]
+    fn test_sort() {
+        let nums = &mut [3, 5, 2, 8, 6, 9, 0, 1, 4, 7];
+        sort(nums, Order::Ascending, &NoopContext);
+        assert!(is_sorted(nums));
+        assert!(has_nums(nums));
+    }
+
+    #[test]
+    fn test_sort_duplicates() {
+        let nums = &mut [2, 0, 1, 2, 2, 0, 1, 1, 2, 0, 0, 2];
+        sort(nums, Order::Ascending, &NoopContext);
+        assert_eq!(&[0, 0, 0, 0, 1, 1, 1, 2, 2, 2, 2, 2], nums);
+    }
+}
@@ -0,0 +1,495 @@
+use super::{
+    binary_insertion_sort, Algorithm, AlgorithmContext, Complexity, Family,
+    Operation::{Compare, Load, Noop, Run, Store, Swap, Write},
+    OperationKind, Order,
+};
+
+pub const NAME: &str = "tim sort";
+
+pub const ALGORITHM: Algorithm = Algorithm {
+    name: NAME,
+    family: Family::Hybrid,
+    stable: true,
+    in_place: false,
+    memory: "O(n)",
+    complexity: Complexity {
+        best: "O(n)",
+        average: "O(n log n)",
+        worst: "O(n log n)",
+    },
+    description: "Detects natural runs, extends short ones with binary insertion sort and merges them with galloping, copying the shorter run of each merge into a buffer.",
+    operations: &[
+        OperationKind::Compare,
+        OperationKind::Swap,
+        OperationKind::Insert,
+        OperationKind::Write,
+        OperationKind::Run,
+        OperationKind::Store,
+        OperationKind::Load,
+    ],
+    sort: &sort,
+};
+
+// Arrays shorter than this are sorted as a single run. Production implementations
+// use 32 or 64, a smaller value makes merges happen on small arrays.
+const MIN_MERGE: usize = 16;
+
+const MIN_GALLOP: usize = 7;
+
+/// What merges share: the buffer the shorter run is copied into, and how many wins
+/// in a row start galloping.
+struct Merges {
+    buffer: Vec<Option<i32>>,
+    min_gallop: usize,
+}
+
+pub fn sort(nums: &mut [i32], order: Order, ctx: &dyn AlgorithmContext) {
+    let n = nums.len();
+    let min_run = min_run_length(n);
+    let mut runs: Vec<(usize, usize)> = Vec::new();
+    let mut merges = Merges {
+        buffer: vec![None; n],
+        min_gallop: MIN_GALLOP,
+    };
+
+    let mut low = 0;
+    while low < n {
+        let mut run_len = count_run_and_make_ascending(nums, low, n, order, ctx);
+        if run_len < min_run {
+            let force = min_run.min(n - low);
+            binary_insertion_sort::sort_range(nums, low, low + run_len, low + force, order, ctx);
+            run_len = force;
+        }
+        ctx.next(Run(low, low + run_len), nums.to_vec());
+
+        runs.push((low, run_len));
+        merge_collapse(nums, &mut merges, &mut runs, order, ctx);
+        low += run_len;
+    }
+
+    while runs.len() > 1 {
+        let mut i = runs.len() - 2;
+        if i > 0 && runs[i - 1].1 < runs[i + 1].1 {
+            i -= 1;
+        }
+        merge_at(nums, &mut merges, &mut runs, i, order, ctx);
+    }
+
+    ctx.next_buffered(Noop(), nums.to_vec(), merges.buffer);
+}
+
+fn min_run_length(mut n: usize) -> usize {
+    let mut r = 0;
+    while n >= MIN_MERGE {
+        r |= n & 1;
+        n >>= 1;
+    }
+    return n + r;
+}
+
+/// Returns the length of the run starting at `low`, reversing it if it is strictly descending.
+fn count_run_and_make_ascending(
+    nums: &mut [i32],
+    low: usize,
+    high: usize,
+    order: Order,
+    ctx: &dyn AlgorithmContext,
+) -> usize {
+    let mut run_high = low + 1;
+    if run_high == high {
+        return 1;
+    }
+
+    ctx.next(Compare(run_high - 1, run_high), nums.to_vec());
+    if order.greater(nums[run_high - 1], nums[run_high]) {
+        run_high += 1;
+        while run_high < high {
+            ctx.next(Compare(run_high - 1, run_high), nums.to_vec());
+            if !order.greater(nums[run_high - 1], nums[run_high]) {
+                break;
+            }
+            run_high += 1;
+        }
+        let (mut i, mut j) = (low, run_high - 1);
+        while i < j {
+            nums.swap(i, j);
+            ctx.next(Swap(i, j), nums.to_vec());
+            i += 1;
+            j -= 1;
+        }
+    } else {
+        run_high += 1;
+        while run_high < high {
+            ctx.next(Compare(run_high - 1, run_high), nums.to_vec());
+            if order.greater(nums[run_high - 1], nums[run_high]) {
+                break;
+            }
+            run_high += 1;
+        }
+    }
+
+    return run_high - low;
+}
+
+/// Merges runs until the lengths on the stack decrease faster than the Fibonacci numbers.
+fn merge_collapse(
+    nums: &mut [i32],
+    merges: &mut Merges,
+    runs: &mut Vec<(usize, usize)>,
+    order: Order,
+    ctx: &dyn AlgorithmContext,
+) {
+    while runs.len() > 1 {
+        let mut i = runs.len() - 2;
+        if (i > 0 && runs[i - 1].1 <= runs[i].1 + runs[i + 1].1)
+            || (i > 1 && runs[i - 2].1 <= runs[i - 1].1 + runs[i].1)
+        {
+            if runs[i - 1].1 < runs[i + 1].1 {
+                i -= 1;
+            }
+        } else if runs[i].1 > runs[i + 1].1 {
+            break;
+        }
+        merge_at(nums, merges, runs, i, order, ctx);
+    }
+}
+
+/// Merges the runs at `i` and `i + 1` of the stack.
+fn merge_at(
+    nums: &mut [i32],
+    merges: &mut Merges,
+    runs: &mut Vec<(usize, usize)>,
+    i: usize,
+    order: Order,
+    ctx: &dyn AlgorithmContext,
+) {
+    let (base, len1) = runs[i];
+    let (mid, len2) = runs[i + 1];
+    let high = mid + len2;
+    runs[i] = (base, len1 + len2);
+    runs.remove(i + 1);
+
+    // Elements of the first run not greater than the head of the second are in place.
+    let low = base + gallop_right(nums, mid, base, mid, order, ctx);
+    if low == mid {
+        ctx.next(Run(base, high), nums.to_vec());
+        return;
+    }
+    // Elements of the second run not less than the tail of the first are in place.
+    let high_merge = mid + gallop_left(nums, mid - 1, mid, high, order, ctx);
+    if high_merge > mid {
+        merge(nums, merges, low, mid, high_merge, order, ctx);
+    }
+    ctx.next(Run(base, high), nums.to_vec());
+}
+
+/// Merges the sorted ranges `low..mid` and `mid..high` through `buffer`, copying
+/// out the shorter one and filling the array from the end that it leaves free.
+fn merge(
+    nums: &mut [i32],
+    merges: &mut Merges,
+    low: usize,
+    mid: usize,
+    high: usize,
+    order: Order,
+    ctx: &dyn AlgorithmContext,
+) {
+    let range = if mid - low <= high - mid {
+        low..mid
+    } else {
+        mid..high
+    };
+    for k in range {
+        merges.buffer[k] = Some(nums[k]);
+        ctx.next_buffered(Store(k, k), nums.to_vec(), merges.buffer.clone());
+    }
+    if mid - low <= high - mid {
+        merge_low(nums, merges, low, mid, high, order, ctx);
+    } else {
+        merge_high(nums, merges, low, mid, high, order, ctx);
+    }
+}
+
+/// Merges the first run, held in `buffer[low..mid]`, with `nums[mid..high]` from
+/// the left, galloping when one side keeps winning.
+fn merge_low(
+    nums: &mut [i32],
+    merges: &mut Merges,
+    low: usize,
+    mid: usize,
+    high: usize,
+    order: Order,
+    ctx: &dyn AlgorithmContext,
+) {
+    let (mut i, mut j, mut k) = (low, mid, low);
+    let (mut wins1, mut wins2) = (0, 0);
+    let Merges { buffer, min_gallop } = merges;
+    let load = |nums: &mut [i32], buffer: &mut [Option<i32>], from: usize, to: usize| {
+        nums[to] = buffer[from].take().unwrap();
+        ctx.next_buffered(Load(from, to), nums.to_vec(), buffer.to_vec());
+    };
+    let write = |nums: &mut [i32], buffer: &[Option<i32>], from: usize, to: usize| {
+        nums[to] = nums[from];
+        ctx.next_buffered(Write(to), nums.to_vec(), buffer.to_vec());
+    };
+
+    while i < mid && j < high {
+        if wins1 < *min_gallop && wins2 < *min_gallop {
+            ctx.next_buffered(Compare(i, j), nums.to_vec(), buffer.to_vec());
+            // Taking from the first run on ties keeps the sort stable.
+            if order.greater(buffer[i].unwrap(), nums[j]) {
+                write(nums, buffer, j, k);
+                j += 1;
+                wins1 = 0;
+                wins2 += 1;
+            } else {
+                load(nums, buffer, i, k);
+                i += 1;
+                wins1 += 1;
+                wins2 = 0;
+            }
+            k += 1;
+            continue;
+        }
+
+        // Take the elements of the first run that stay before the head of the second.
+        let count1 = gallop(i, mid, |x| {
+            ctx.next_buffered(Compare(x, j), nums.to_vec(), buffer.to_vec());
+            order.greater(buffer[x].unwrap(), nums[j])
+        });
+        for _ in 0..count1 {
+            load(nums, buffer, i, k);
+            i += 1;
+            k += 1;
+        }
+        if i == mid {
+            break;
+        }
+        // Take the elements of the second run that belong before the head of the first.
+        let count2 = gallop(j, high, |x| {
+            ctx.next_buffered(Compare(x, i), nums.to_vec(), buffer.to_vec());
+            !order.less(nums[x], buffer[i].unwrap())
+        });
+        for _ in 0..count2 {
+            write(nums, buffer, j, k);
+            j += 1;
+            k += 1;
+        }
+        adapt_min_gallop(min_gallop, count1, count2, &mut wins1, &mut wins2);
+    }
+    // The rest of the second run is already in place.
+    while i < mid {
+        load(nums, buffer, i, k);
+        i += 1;
+        k += 1;
+    }
+}
+
+/// Merges `nums[low..mid]` with the second run, held in `buffer[mid..high]`, from
+/// the right, galloping when one side keeps winning.
+fn merge_high(
+    nums: &mut [i32],
+    merges: &mut Merges,
+    low: usize,
+    mid: usize,
+    high: usize,
+    order: Order,
+    ctx: &dyn AlgorithmContext,
+) {
+    // One past the last element left of each run and of the free space.
+    let (mut i, mut j, mut k) = (mid, high, high);
+    let (mut wins1, mut wins2) = (0, 0);
+    let Merges { buffer, min_gallop } = merges;
+    let load = |nums: &mut [i32], buffer: &mut [Option<i32>], from: usize, to: usize| {
+        nums[to] = buffer[from].take().unwrap();
+        ctx.next_buffered(Load(from, to), nums.to_vec(), buffer.to_vec());
+    };
+    let write = |nums: &mut [i32], buffer: &[Option<i32>], from: usize, to: usize| {
+        nums[to] = nums[from];
+        ctx.next_buffered(Write(to), nums.to_vec(), buffer.to_vec());
+    };
+
+    while i > low && j > mid {
+        if wins1 < *min_gallop && wins2 < *min_gallop {
+            ctx.next_buffered(Compare(i - 1, j - 1), nums.to_vec(), buffer.to_vec());
+            // Taking from the second run on ties keeps the sort stable.
+            if order.greater(nums[i - 1], buffer[j - 1].unwrap()) {
+                write(nums, buffer, i - 1, k - 1);
+                i -= 1;
+                wins1 += 1;
+                wins2 = 0;
+            } else {
+                load(nums, buffer, j - 1, k - 1);
+                j -= 1;
+                wins1 = 0;
+                wins2 += 1;
+            }
+            k -= 1;
+            continue;
+        }
+
+        // Take the elements of the first run that belong after the tail of the second.
+        let count1 = gallop(0, i - low, |x| {
+            ctx.next_buffered(Compare(i - 1 - x, j - 1), nums.to_vec(), buffer.to_vec());
+            !order.greater(nums[i - 1 - x], buffer[j - 1].unwrap())
+        });
+        for _ in 0..count1 {
+            write(nums, buffer, i - 1, k - 1);
+            i -= 1;
+            k -= 1;
+        }
+        if i == low {
+            break;
+        }
+        // Take the elements of the second run that stay after the tail of the first.
+        let count2 = gallop(0, j - mid, |x| {
+            ctx.next_buffered(Compare(j - 1 - x, i - 1), nums.to_vec(), buffer.to_vec());
+            order.less(buffer[j - 1 - x].unwrap(), nums[i - 1])
+        });
+        for _ in 0..count2 {
+            load(nums, buffer, j - 1, k - 1);
+            j -= 1;
+            k -= 1;
+        }
+        adapt_min_gallop(min_gallop, count1, count2, &mut wins1, &mut wins2);
+    }
+    // The rest of the first run is already in place.
+    while j > mid {
+        load(nums, buffer, j - 1, k - 1);
+        j -= 1;
+        k -= 1;
+    }
+}
+
+/// Makes galloping harder to enter after it took few elements, and easier after
+/// it took many.
+fn adapt_min_gallop(
+    min_gallop: &mut usize,
+    count1: usize,
+    count2: usize,
+    wins1: &mut usize,
+    wins2: &mut usize,
+) {
+    if count1 < MIN_GALLOP && count2 < MIN_GALLOP {
+        *min_gallop += 1;
+        *wins1 = 0;
+        *wins2 = 0;
+    } else {
+        *min_gallop = (*min_gallop).saturating_sub(1).max(1);
+    }
+}
+
+/// Counts the leading elements of `low..high` that are not greater than `nums[key]`.
+fn gallop_right(
+    nums: &[i32],
+    key: usize,
+    low: usize,
+    high: usize,
+    order: Order,
+    ctx: &dyn AlgorithmContext,
+) -> usize {
+    return gallop(low, high, |i| {
+        ctx.next(Compare(i, key), nums.to_vec());
+        order.greater(nums[i], nums[key])
+    });
+}
+
+/// Counts the leading elements of `low..high` that are less than `nums[key]`.
+fn gallop_left(
+    nums: &[i32],
+    key: usize,
+    low: usize,
+    high: usize,
+    order: Order,
+    ctx: &dyn AlgorithmContext,
+) -> usize {
+    return gallop(low, high, |i| {
+        ctx.next(Compare(i, key), nums.to_vec());
+        !order.less(nums[i], nums[key])
+    });
+}
+
+/// Finds the first index in `low..high` where `stop` holds with an exponential search
+/// followed by a binary search, and returns its offset from `low`.
+fn gallop(low: usize, high: usize, mut stop: impl FnMut(usize) -> bool) -> usize {
+    let len = high - low;
+    let (mut left, mut right) = (0, len);
+    let mut offset = 1;
+    while offset <= len {
+        if stop(low + offset - 1) {
+            right = offset - 1;
+            break;
+        }
+        left = offset;
+        offset *= 2;
+    }
+    while left < right {
+        let mid = left + (right - left) / 2;
+        if stop(low + mid) {
+            right = mid;
+        } else {
+            left = mid + 1;
+        }
+    }
+    return left;
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sorting::has_nums;
+    use crate::sorting::is_sorted;
+    use crate::sorting::NoopContext;
+    use crate::sorting::{record, validate::validate, Operation};
+    use crate::{init_vec, shuffle};
+
+    use super::*;
+
+    #[test]
+    fn test_sort() {
+        let nums = &mut [3, 5, 2, 8, 6, 9, 0, 1, 4, 7];
+        sort(nums, Order::Ascending, &NoopContext);
+        assert!(is_sorted(nums));
+        assert!(has_nums(nums));
+    }
+
+    #[test]
+    fn test_sort_runs() {
+        let mut nums = init_vec(100);
+        nums[..40].reverse();
+        shuffle(&mut nums[60..]);
+        sort(&mut nums, Order::Ascending, &NoopContext);
+        assert_eq!(init_vec(100), nums);
+    }
+
+    #[test]
+    fn test_sort_stable() {
+        // Sorts by the hundreds digit, the rest records the original position.
+        let order = Order::Custom(|a, b| (a / 100).cmp(&(b / 100)));
+        let mut nums: Vec<i32> = (0..100).map(|i| (i * 7 % 5) * 100 + i).collect();
+        let mut expected = nums.clone();
+        expected.sort_by_key(|n| n / 100);
+        sort(&mut nums, order, &NoopContext);
+        assert_eq!(expected, nums);
+    }
+
+    #[test]
+    fn test_sort_gallop() {
+        // Two runs taking turns in blocks of 50, the shorter one first and then
+        // second. Its elements left in place by trimming the merge are not copied.
+        for (shorter_first, copied) in [(true, 50), (false, 100)] {
+            let (mut nums, rest): (Vec<i32>, Vec<i32>) =
+                (0..300).partition(|v| (v / 50 % 3 == 0) == shorter_first);
+            nums.extend(rest);
+            let steps = record(&ALGORITHM, &nums, Order::Ascending);
+            assert!(validate(&steps, Order::Ascending).is_ok());
+            let last = steps.last().unwrap();
+            assert_eq!((0..300).collect::<Vec<i32>>(), last.nums);
+            assert!(last.buffer.as_ref().unwrap().iter().all(Option::is_none));
+            let stores = steps
+                .iter()
+                .filter(|step| matches!(step.operation, Operation::Store(_, _)))
+                .count();
+            assert_eq!(copied, stores);
+        }
+    }
+}
@@ -0,0 +1,261 @@
+//! Replays a recorded trace from its first array, applying what each operation
+//! claims to do, and finds the first step whose recorded array or buffer differs.
+
+use std::fmt::Display;
+
+use super::{
+    Operation::{
+        self, Compare, Enter, Exit, Heap, Insert, Layer, Load, Noop, Partition, Run, Store, Swap,
+        Write,
+    },
+    Order, Step,
+};
+
+/// A step of a trace that does not follow from the steps before it.
+#[derive(Debug)]
+pub struct Divergence {
+    /// Index of the step in the trace.
+    pub step: usize,
+    pub reason: String,
+}
+
+impl Display for Divergence {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "step {}: {}", self.step, self.reason)
+    }
+}
+
+impl std::error::Error for Divergence {}
+
+/// Checks that every step of `steps`, as returned by `record`, follows from the
+/// one before. `order` is the order sorted in, which decides the exchanges of
+/// layers.
+pub fn validate(steps: &[Step], order: Order) -> Result<(), Divergence> {
+    let Some(first) = steps.first() else {
+        return Ok(());
+    };
+    let mut nums = first.nums.clone();
+    let mut buffer = first.buffer.clone();
+    for (i, step) in steps.iter().enumerate().skip(1) {
+        let diverge = |reason: String| Divergence { step: i, reason };
+        if step.nums.len() != nums.len() {
+            return Err(diverge(format!(
+                "recorded {} elements instead of {}",
+                step.nums.len(),
+                nums.len()
+            )));
+        }
+        // Buffered algorithms report their buffer from their first step on.
+        if let (None, Some(recorded)) = (&buffer, &step.buffer) {
+            buffer = Some(vec![None; recorded.len()]);
+        }
+        apply(&step.operation, &mut nums, &mut buffer, &step.nums, order).map_err(diverge)?;
+        if nums != step.nums {
+            return Err(diverge(format!(
+                "{} gives {:?} but {:?} was recorded",
+                step.operation, nums, step.nums
+            )));
+        }
+        if let Some(recorded) = &step.buffer {
+            if buffer.as_ref() != Some(recorded) {
+                return Err(diverge(format!(
+                    "{} gives the buffer {:?} but {:?} was recorded",
+                    step.operation, buffer, recorded
+                )));
+            }
+        }
+    }
+    return Ok(());
+}
+
+/// Applies `operation` to the array and buffer, with `recorded` standing in for
+/// what operations that move or write elements at will put there.
+fn apply(
+    operation: &Operation,
+    nums: &mut Vec<i32>,
+    buffer: &mut Option<Vec<Option<i32>>>,
+    recorded: &[i32],
+    order: Order,
+) -> Result<(), String> {
+    let n = nums.len();
+    let index = |i: usize| {
+        if i < n {
+            return Ok(i);
+        }
+        return Err(format!("{} is out of bounds of {} elements", operation, n));
+    };
+    let range = |start: usize, end: usize| {
+        if start <= end && end <= n {
+            return Ok(());
+        }
+        return Err(format!("{} is out of bounds of {} elements", operation, n));
+    };
+    match *operation {
+        Noop() => {}
+        Compare(a, b) => {
+            index(a)?;
+            index(b)?;
+        }
+        Run(start, end) | Heap(start, end) | Enter(start, end) | Exit(start, end) => {
+            range(start, end)?
+        }
+        Partition(start, end, pivot) => {
+            range(start, end)?;
+            index(pivot)?;
+        }
+        Swap(a, b) => nums.swap(index(a)?, index(b)?),
+        Insert(i) => {
+            if !inserted(nums, recorded, index(i)?) {
+                return Err(format!(
+                    "{} does not move one element to {} from {:?} to {:?}",
+                    operation, i, nums, recorded
+                ));
+            }
+            nums.copy_from_slice(recorded);
+        }
+        Write(i) => nums[index(i)?] = recorded[i],
+        Store(from, to) => {
+            let value = nums[index(from)?];
+            let slot = slot(buffer, to, operation)?;
+            *slot = Some(value);
+        }
+        Load(from, to) => {
+            let to = index(to)?;
+            let slot = slot(buffer, from, operation)?;
+            nums[to] = slot
+                .take()
+                .ok_or_else(|| format!("{} loads an empty slot", operation))?;
+        }
+        Layer(ref pairs) => {
+            for &(a, b) in pairs {
+                if order.greater(nums[index(a)?], nums[index(b)?]) {
+                    nums.swap(a, b);
+                }
+            }
+        }
+    }
+    return Ok(());
+}
+
+fn slot<'a>(
+    buffer: &'a mut Option<Vec<Option<i32>>>,
+    i: usize,
+    operation: &Operation,
+) -> Result<&'a mut Option<i32>, String> {
+    let Some(buffer) = buffer else {
+        return Err(format!("{} without a buffer", operation));
+    };
+    let len = buffer.len();
+    return buffer
+        .get_mut(i)
+        .ok_or_else(|| format!("{} is out of bounds of a buffer of {}", operation, len));
+}
+
+/// Whether `after` is `before` with one element moved to `i`, shifting the ones in
+/// between by one.
+fn inserted(before: &[i32], after: &[i32], i: usize) -> bool {
+    let changed = |k: &usize| before[*k] != after[*k];
+    let (Some(low), Some(high)) = (
+        (0..before.len()).find(changed),
+        (0..before.len()).rfind(changed),
+    ) else {
+        return true;
+    };
+    if i <= low {
+        // Moved left from `high`, or from further right past elements equal to it.
+        return after[i] == before[high] && after[i + 1..=high] == before[i..high];
+    }
+    if i >= high {
+        return after[i] == before[low] && after[low..i] == before[low + 1..=i];
+    }
+    return false;
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, seq::SliceRandom, Rng, SeedableRng};
+
+    use super::*;
+    use crate::sorting::{get_algorithms, record};
+
+    #[test]
+    fn test_validate_algorithms() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let mut shuffled: Vec<i32> = (0..50).collect();
+        shuffled.shuffle(&mut rng);
+        let duplicates: Vec<i32> = (0..50).map(|_| rng.gen_range(-5..5)).collect();
+        let orders = [Order::Ascending, Order::Descending, Order::Modulo(3)];
+        for algorithm in get_algorithms() {
+            for nums in [&shuffled, &duplicates] {
+                for order in orders {
+                    let steps = record(algorithm, nums, order);
+                    if let Err(divergence) = validate(&steps, order) {
+                        panic!("{} {}: {}", algorithm, order, divergence);
+                    }
+                }
+            }
+        }
+    }
+
+    fn trace(steps: &[(Operation, &[i32])]) -> Vec<Step> {
+        return steps
+            .iter()
+            .map(|(operation, nums)| Step::new(operation.clone(), nums.to_vec()))
+            .collect();
+    }
+
+    #[test]
+    fn test_validate_divergence() {
+        let steps = trace(&[
+            (Noop(), &[3, 1, 2]),
+            (Swap(0, 1), &[1, 3, 2]),
+            (Swap(1, 2), &[1, 3, 2]),
+        ]);
+        let divergence = validate(&steps, Order::Ascending).unwrap_err();
+        assert_eq!(2, divergence.step);
+        assert_eq!(
+            "step 2: swap: 1 2 gives [1, 2, 3] but [1, 3, 2] was recorded",
+            divergence.to_string()
+        );
+
+        let steps = trace(&[(Noop(), &[3, 1, 2]), (Compare(0, 3), &[3, 1, 2])]);
+        assert_eq!(1, validate(&steps, Order::Ascending).unwrap_err().step);
+        let steps = trace(&[(Noop(), &[1]), (Load(0, 0), &[1])]);
+        let divergence = validate(&steps, Order::Ascending).unwrap_err();
+        assert_eq!(
+            "step 1: load: 0 -> 0 without a buffer",
+            divergence.to_string()
+        );
+        assert!(validate(&[], Order::Ascending).is_ok());
+    }
+
+    #[test]
+    fn test_validate_insert() {
+        let steps = trace(&[
+            (Noop(), &[2, 3, 4, 1]),
+            (Insert(0), &[1, 2, 3, 4]),
+            (Insert(3), &[2, 3, 4, 1]),
+        ]);
+        assert!(validate(&steps, Order::Ascending).is_ok());
+        // Two elements moving at once is not an insertion.
+        let steps = trace(&[(Noop(), &[3, 4, 1, 2]), (Insert(0), &[1, 2, 3, 4])]);
+        assert_eq!(1, validate(&steps, Order::Ascending).unwrap_err().step);
+        // Equal elements leave the origin ambiguous.
+        let steps = trace(&[(Noop(), &[1, 3, 1, 1]), (Insert(0), &[1, 1, 3, 1])]);
+        assert!(validate(&steps, Order::Ascending).is_ok());
+    }
+
+    #[test]
+    fn test_validate_buffer() {
+        let mut steps = trace(&[
+            (Noop(), &[2, 1]),
+            (Store(0, 0), &[2, 1]),
+            (Load(0, 1), &[2, 2]),
+        ]);
+        steps[1].buffer = Some(vec![Some(2), None]);
+        steps[2].buffer = Some(vec![None, None]);
+        assert!(validate(&steps, Order::Ascending).is_ok());
+        steps[2].buffer = Some(vec![Some(2), None]);
+        assert_eq!(2, validate(&steps, Order::Ascending).unwrap_err().step);
+    }
+}
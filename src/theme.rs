@@ -0,0 +1,386 @@
+//! Colours of the highlighted elements, built in or defined in the config file, and
+//! a monochrome mode that marks them with glyphs instead.
+
+use std::str::FromStr;
+
+use anyhow::{anyhow, Result};
+use ratatui::style::{Color, Modifier, Style};
+use serde::{Deserialize, Serialize};
+
+/// What an element or a part of a panel is highlighted as.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Highlight {
+    Compare,
+    Swap,
+    Insert,
+    Run,
+    Partition,
+    Pivot,
+    Heap,
+    /// A subproblem still on the call stack.
+    Call,
+    /// The innermost subproblem on the call stack.
+    CurrentCall,
+    /// The worker of a parallel algorithm that last touched an element.
+    Worker(usize),
+    /// Parts that no longer matter, such as nodes outside of the heap.
+    Dim,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Palette {
+    pub compare: Color,
+    pub swap: Color,
+    pub insert: Color,
+    pub run: Color,
+    pub partition: Color,
+    pub pivot: Color,
+    pub heap: Color,
+    pub call: Color,
+    pub current_call: Color,
+    pub dim: Color,
+    /// Text of the algorithm menu.
+    pub text: Color,
+    /// Background of the selected algorithm.
+    pub selection: Color,
+    pub workers: Vec<Color>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Theme {
+    pub name: String,
+    /// `None` draws without colours.
+    pub palette: Option<Palette>,
+}
+
+/// Glyphs of the workers of a parallel algorithm without colours, repeated for
+/// more workers.
+const WORKER_GLYPHS: [char; 4] = ['\u{259A}', '\u{259E}', '\u{2599}', '\u{259F}'];
+
+pub const BUILTIN: [&str; 5] = ["dark", "light", "high-contrast", "colorblind", "mono"];
+
+impl Default for Theme {
+    fn default() -> Self {
+        return Theme::builtin("dark").unwrap();
+    }
+}
+
+impl Theme {
+    pub fn builtin(name: &str) -> Option<Theme> {
+        let palette = match name {
+            "dark" => Some(Palette {
+                compare: Color::LightCyan,
+                swap: Color::LightGreen,
+                insert: Color::LightYellow,
+                run: Color::LightMagenta,
+                partition: Color::LightBlue,
+                pivot: Color::LightMagenta,
+                heap: Color::LightRed,
+                call: Color::LightCyan,
+                current_call: Color::LightMagenta,
+                dim: Color::DarkGray,
+                text: Color::White,
+                selection: Color::DarkGray,
+                workers: vec![Color::Red, Color::Green, Color::Blue, Color::Yellow],
+            }),
+            "light" => Some(Palette {
+                compare: Color::Blue,
+                swap: Color::Green,
+                insert: Color::Rgb(176, 112, 0),
+                run: Color::Magenta,
+                partition: Color::Cyan,
+                pivot: Color::Magenta,
+                heap: Color::Red,
+                call: Color::Blue,
+                current_call: Color::Magenta,
+                dim: Color::Gray,
+                text: Color::Black,
+                selection: Color::Gray,
+                workers: vec![
+                    Color::Red,
+                    Color::Green,
+                    Color::Blue,
+                    Color::Rgb(176, 112, 0),
+                ],
+            }),
+            "high-contrast" => Some(Palette {
+                compare: Color::Rgb(0, 255, 255),
+                swap: Color::Rgb(0, 255, 0),
+                insert: Color::Rgb(255, 255, 0),
+                run: Color::Rgb(255, 0, 255),
+                partition: Color::Rgb(0, 128, 255),
+                pivot: Color::Rgb(255, 0, 255),
+                heap: Color::Rgb(255, 64, 64),
+                call: Color::Rgb(0, 255, 255),
+                current_call: Color::Rgb(255, 0, 255),
+                dim: Color::Gray,
+                text: Color::Rgb(255, 255, 255),
+                selection: Color::Blue,
+                workers: vec![
+                    Color::Rgb(255, 64, 64),
+                    Color::Rgb(0, 255, 0),
+                    Color::Rgb(0, 128, 255),
+                    Color::Rgb(255, 255, 0),
+                ],
+            }),
+            // The Okabe-Ito palette, told apart with any kind of colour blindness.
+            "colorblind" => Some(Palette {
+                compare: Color::Rgb(86, 180, 233),
+                swap: Color::Rgb(0, 158, 115),
+                insert: Color::Rgb(240, 228, 66),
+                run: Color::Rgb(204, 121, 167),
+                partition: Color::Rgb(0, 114, 178),
+                pivot: Color::Rgb(213, 94, 0),
+                heap: Color::Rgb(230, 159, 0),
+                call: Color::Rgb(86, 180, 233),
+                current_call: Color::Rgb(204, 121, 167),
+                dim: Color::DarkGray,
+                text: Color::White,
+                selection: Color::DarkGray,
+                workers: vec![
+                    Color::Rgb(230, 159, 0),
+                    Color::Rgb(86, 180, 233),
+                    Color::Rgb(0, 158, 115),
+                    Color::Rgb(240, 228, 66),
+                ],
+            }),
+            "mono" => None,
+            _ => return None,
+        };
+        return Some(Theme {
+            name: name.to_string(),
+            palette,
+        });
+    }
+
+    /// Whether the theme draws with colours.
+    pub fn colored(&self) -> bool {
+        return self.palette.is_some();
+    }
+
+    pub fn color(&self, highlight: Highlight) -> Option<Color> {
+        let palette = self.palette.as_ref()?;
+        return Some(match highlight {
+            Highlight::Compare => palette.compare,
+            Highlight::Swap => palette.swap,
+            Highlight::Insert => palette.insert,
+            Highlight::Run => palette.run,
+            Highlight::Partition => palette.partition,
+            Highlight::Pivot => palette.pivot,
+            Highlight::Heap => palette.heap,
+            Highlight::Call => palette.call,
+            Highlight::CurrentCall => palette.current_call,
+            Highlight::Dim => palette.dim,
+            Highlight::Worker(worker) => *palette.workers.get(worker % palette.workers.len())?,
+        });
+    }
+
+    /// The glyph drawn in place of a highlighted element of a chart, only without
+    /// colours.
+    pub fn glyph(&self, highlight: Highlight) -> Option<char> {
+        if self.colored() {
+            return None;
+        }
+        return match highlight {
+            Highlight::Compare => Some('\u{2592}'),
+            Highlight::Swap => Some('\u{2593}'),
+            Highlight::Insert => Some('\u{2591}'),
+            Highlight::Run => Some('\u{2503}'),
+            Highlight::Partition => Some('\u{2502}'),
+            Highlight::Pivot => Some('\u{25C6}'),
+            Highlight::Heap => Some('\u{254F}'),
+            Highlight::Worker(worker) => Some(WORKER_GLYPHS[worker % WORKER_GLYPHS.len()]),
+            _ => None,
+        };
+    }
+
+    /// Style of highlighted text. Without colours highlights are reversed, open
+    /// calls bold and dimmed parts dim.
+    pub fn style(&self, highlight: Option<Highlight>) -> Style {
+        let Some(highlight) = highlight else {
+            return Style::default();
+        };
+        if let Some(color) = self.color(highlight) {
+            return Style::default().fg(color);
+        }
+        return match highlight {
+            Highlight::Worker(_) => Style::default(),
+            Highlight::Dim => Style::default().add_modifier(Modifier::DIM),
+            Highlight::Call => Style::default().add_modifier(Modifier::BOLD),
+            _ => Style::default().add_modifier(Modifier::REVERSED),
+        };
+    }
+
+    /// Style of the algorithm menu.
+    pub fn text_style(&self) -> Style {
+        return match &self.palette {
+            Some(palette) => Style::default().fg(palette.text),
+            None => Style::default(),
+        };
+    }
+
+    /// Style of the selected algorithm of the menu.
+    pub fn selection_style(&self) -> Style {
+        let style = Style::default().add_modifier(Modifier::BOLD);
+        return match &self.palette {
+            Some(palette) => style.bg(palette.selection),
+            None => style.add_modifier(Modifier::REVERSED),
+        };
+    }
+}
+
+/// A theme of the config file. Colours left out are taken from the `base` theme,
+/// `dark` by default.
+#[derive(Debug, Default, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ThemeConfig {
+    base: Option<String>,
+    compare: Option<String>,
+    swap: Option<String>,
+    insert: Option<String>,
+    run: Option<String>,
+    partition: Option<String>,
+    pivot: Option<String>,
+    heap: Option<String>,
+    call: Option<String>,
+    current_call: Option<String>,
+    dim: Option<String>,
+    text: Option<String>,
+    selection: Option<String>,
+    workers: Option<Vec<String>>,
+}
+
+impl ThemeConfig {
+    pub fn theme(&self, name: &str) -> Result<Theme> {
+        let base = self.base.as_deref().unwrap_or("dark");
+        let mut palette = Theme::builtin(base)
+            .and_then(|theme| theme.palette)
+            .ok_or_else(|| {
+                anyhow!(
+                    "theme '{}' must be based on one of dark, light, high-contrast or colorblind",
+                    name
+                )
+            })?;
+        let parse = |value: &str| {
+            return Color::from_str(value)
+                .map_err(|_| anyhow!("invalid colour '{}' in theme '{}'", value, name));
+        };
+        let colors = [
+            (&self.compare, &mut palette.compare),
+            (&self.swap, &mut palette.swap),
+            (&self.insert, &mut palette.insert),
+            (&self.run, &mut palette.run),
+            (&self.partition, &mut palette.partition),
+            (&self.pivot, &mut palette.pivot),
+            (&self.heap, &mut palette.heap),
+            (&self.call, &mut palette.call),
+            (&self.current_call, &mut palette.current_call),
+            (&self.dim, &mut palette.dim),
+            (&self.text, &mut palette.text),
+            (&self.selection, &mut palette.selection),
+        ];
+        for (value, color) in colors {
+            if let Some(value) = value {
+                *color = parse(value)?;
+            }
+        }
+        if let Some(workers) = &self.workers {
+            if workers.is_empty() {
+                return Err(anyhow!("theme '{}' needs at least one worker colour", name));
+            }
+            palette.workers = workers
+                .iter()
+                .map(|value| parse(value))
+                .collect::<Result<_>>()?;
+        }
+        return Ok(Theme {
+            name: name.to_string(),
+            palette: Some(palette),
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builtin() {
+        for name in BUILTIN {
+            assert_eq!(name, Theme::builtin(name).unwrap().name);
+        }
+        assert!(Theme::builtin("solarized").is_none());
+        assert_eq!(
+            Some(Color::LightCyan),
+            Theme::default().color(Highlight::Compare)
+        );
+    }
+
+    #[test]
+    fn test_mono() {
+        let mono = Theme::builtin("mono").unwrap();
+        assert_eq!(None, mono.color(Highlight::Swap));
+        assert_eq!(Some('▓'), mono.glyph(Highlight::Swap));
+        assert_eq!(None, Theme::default().glyph(Highlight::Swap));
+        assert_eq!(
+            Style::default().add_modifier(Modifier::REVERSED),
+            mono.style(Some(Highlight::Compare))
+        );
+
+        // Workers are told apart from each other and from the other highlights.
+        let glyphs: Vec<char> = (0..4)
+            .map(|worker| mono.glyph(Highlight::Worker(worker)).unwrap())
+            .collect();
+        let mut all: Vec<char> = [
+            Highlight::Compare,
+            Highlight::Swap,
+            Highlight::Insert,
+            Highlight::Run,
+            Highlight::Partition,
+            Highlight::Pivot,
+            Highlight::Heap,
+        ]
+        .into_iter()
+        .filter_map(|highlight| mono.glyph(highlight))
+        .chain(glyphs.iter().copied())
+        .collect();
+        all.sort();
+        all.dedup();
+        assert_eq!(11, all.len());
+        assert_eq!(
+            mono.glyph(Highlight::Worker(0)),
+            mono.glyph(Highlight::Worker(4))
+        );
+        assert_eq!(None, Theme::default().glyph(Highlight::Worker(0)));
+    }
+
+    #[test]
+    fn test_theme_config() {
+        let config: ThemeConfig = toml::from_str(
+            r##"
+            base = "light"
+            compare = "#ff8800"
+            workers = ["red", "bright blue"]
+            "##,
+        )
+        .unwrap();
+        let theme = config.theme("custom").unwrap();
+        let light = Theme::builtin("light").unwrap();
+        assert_eq!(
+            Some(Color::Rgb(255, 136, 0)),
+            theme.color(Highlight::Compare)
+        );
+        assert_eq!(light.color(Highlight::Swap), theme.color(Highlight::Swap));
+        assert_eq!(Some(Color::LightBlue), theme.color(Highlight::Worker(3)));
+
+        let config: ThemeConfig = toml::from_str("swap = \"sparkly\"").unwrap();
+        let error = config.theme("custom").unwrap_err();
+        assert_eq!(
+            "invalid colour 'sparkly' in theme 'custom'",
+            error.to_string()
+        );
+        let config: ThemeConfig = toml::from_str("base = \"mono\"").unwrap();
+        assert!(config.theme("custom").is_err());
+        assert!(toml::from_str::<ThemeConfig>("colour = \"red\"").is_err());
+    }
+}
@@ -0,0 +1,4 @@
+#!/bin/sh
+read -r line
+exec >&-
+exec sleep 5
@@ -0,0 +1,4 @@
+#!/bin/sh
+read -r line
+echo "out of ideas" >&2
+exit 3
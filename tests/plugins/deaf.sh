@@ -0,0 +1,4 @@
+#!/bin/sh
+while true; do
+    echo "compare 0 1"
+done
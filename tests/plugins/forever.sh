@@ -0,0 +1,5 @@
+#!/bin/sh
+read -r line
+while true; do
+    echo "swap 0 1"
+done
@@ -0,0 +1,3 @@
+#!/bin/sh
+read -r line
+exec sleep 5